@@ -15,6 +15,7 @@
 use crate::{db::MultiThreaded, ffi, Options};
 
 use std::sync::Arc;
+use std::time::Duration;
 
 /// The name of the default column family.
 ///
@@ -28,6 +29,7 @@ pub const DEFAULT_COLUMN_FAMILY_NAME: &str = "default";
 pub struct ColumnFamilyDescriptor {
     pub(crate) name: String,
     pub(crate) options: Options,
+    pub(crate) ttl: Option<Duration>,
 }
 
 impl ColumnFamilyDescriptor {
@@ -39,6 +41,24 @@ impl ColumnFamilyDescriptor {
         Self {
             name: name.into(),
             options,
+            ttl: None,
+        }
+    }
+
+    /// Create a new column family descriptor with the specified name, options
+    /// and Time to Live.
+    ///
+    /// The TTL is only used when the database is opened with
+    /// `open_cf_descriptors_with_ttl`, where it overrides the database-wide
+    /// TTL for this column family.
+    pub fn new_with_ttl<S>(name: S, options: Options, ttl: Duration) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            options,
+            ttl: Some(ttl),
         }
     }
 }
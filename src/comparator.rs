@@ -16,6 +16,8 @@
 use libc::{c_char, c_int, c_void, size_t};
 use std::cmp::Ordering;
 use std::ffi::CString;
+use std::panic;
+use std::process;
 use std::slice;
 
 pub type CompareFn = fn(&[u8], &[u8]) -> Ordering;
@@ -45,7 +47,12 @@ pub unsafe extern "C" fn compare_callback(
     let cb: &mut ComparatorCallback = &mut *(raw_cb as *mut ComparatorCallback);
     let a: &[u8] = slice::from_raw_parts(a_raw as *const u8, a_len as usize);
     let b: &[u8] = slice::from_raw_parts(b_raw as *const u8, b_len as usize);
-    match (cb.f)(a, b) {
+    // A panic must not unwind across the FFI boundary. There is no way to report
+    // a failed comparison to RocksDB, and substituting another ordering would
+    // leave keys out of order in memtables and SST files, so abort instead.
+    let f = cb.f;
+    let ordering = panic::catch_unwind(|| f(a, b)).unwrap_or_else(|_| process::abort());
+    match ordering {
         Ordering::Less => -1,
         Ordering::Equal => 0,
         Ordering::Greater => 1,
@@ -23,7 +23,7 @@ use crate::{
     ColumnFamily, ColumnFamilyDescriptor, CompactOptions, DBIteratorWithThreadMode,
     DBPinnableSlice, DBRawIteratorWithThreadMode, DBWALIterator, Direction, Error, FlushOptions,
     IngestExternalFileOptions, IteratorMode, Options, ReadOptions, SnapshotWithThreadMode,
-    WriteBatch, WriteOptions, DEFAULT_COLUMN_FAMILY_NAME,
+    SstFileWriter, WriteBatch, WriteOptions, DEFAULT_COLUMN_FAMILY_NAME,
 };
 
 use libc::{self, c_char, c_int, c_uchar, c_void, size_t};
@@ -37,6 +37,7 @@ use std::path::PathBuf;
 use std::ptr;
 use std::slice;
 use std::str;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::sync::RwLock;
 use std::time::Duration;
@@ -145,6 +146,19 @@ pub trait DBAccess {
         key: K,
         readopts: &ReadOptions,
     ) -> Result<Option<Vec<u8>>, Error>;
+
+    fn get_pinned_opt<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+        readopts: &ReadOptions,
+    ) -> Result<Option<DBPinnableSlice>, Error>;
+
+    fn get_pinned_cf_opt<K: AsRef<[u8]>>(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+        key: K,
+        readopts: &ReadOptions,
+    ) -> Result<Option<DBPinnableSlice>, Error>;
 }
 
 impl<T: ThreadMode> DBAccess for DBWithThreadMode<T> {
@@ -168,6 +182,23 @@ impl<T: ThreadMode> DBAccess for DBWithThreadMode<T> {
     ) -> Result<Option<Vec<u8>>, Error> {
         self.get_cf_opt(cf, key, readopts)
     }
+
+    fn get_pinned_opt<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+        readopts: &ReadOptions,
+    ) -> Result<Option<DBPinnableSlice>, Error> {
+        self.get_pinned_opt(key, readopts)
+    }
+
+    fn get_pinned_cf_opt<K: AsRef<[u8]>>(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+        key: K,
+        readopts: &ReadOptions,
+    ) -> Result<Option<DBPinnableSlice>, Error> {
+        self.get_pinned_cf_opt(cf, key, readopts)
+    }
 }
 
 /// A type alias to DB instance type with the single-threaded column family
@@ -278,6 +309,9 @@ impl<T: ThreadMode> DBWithThreadMode<T> {
 
     /// Opens a database with the given database with a Time to Live compaction filter and
     /// column family descriptors.
+    ///
+    /// Column families described with `ColumnFamilyDescriptor::new_with_ttl` use their own
+    /// TTL, all others use `ttl`.
     pub fn open_cf_descriptors_with_ttl<P, I>(
         opts: &Options,
         path: P,
@@ -402,10 +436,10 @@ impl<T: ThreadMode> DBWithThreadMode<T> {
             let mut cfs_v = cfs;
             // Always open the default column family.
             if !cfs_v.iter().any(|cf| cf.name == DEFAULT_COLUMN_FAMILY_NAME) {
-                cfs_v.push(ColumnFamilyDescriptor {
-                    name: String::from(DEFAULT_COLUMN_FAMILY_NAME),
-                    options: Options::default(),
-                });
+                cfs_v.push(ColumnFamilyDescriptor::new(
+                    DEFAULT_COLUMN_FAMILY_NAME,
+                    Options::default(),
+                ));
             }
             // We need to store our CStrings in an intermediate vector
             // so that their pointers remain valid.
@@ -534,6 +568,12 @@ impl<T: ThreadMode> DBWithThreadMode<T> {
                     ))
                 }
                 AccessType::WithTTL { ttl } => {
+                    // One TTL per column family, falling back to the database-wide TTL.
+                    let ttls: Vec<_> = cfs_v
+                        .iter()
+                        .map(|cf| cf.ttl.unwrap_or(ttl).as_secs() as c_int)
+                        .collect();
+
                     ffi_try!(ffi::rocksdb_open_column_families_with_ttl(
                         opts.inner,
                         cpath.as_ptr(),
@@ -541,7 +581,7 @@ impl<T: ThreadMode> DBWithThreadMode<T> {
                         cfnames.as_ptr(),
                         cfopts.as_ptr(),
                         cfhandles.as_mut_ptr(),
-                        &(ttl.as_secs() as c_int) as *const _,
+                        ttls.as_ptr(),
                     ))
                 }
             }
@@ -641,6 +681,37 @@ impl<T: ThreadMode> DBWithThreadMode<T> {
         self.write_opt(batch, &wo)
     }
 
+    /// Builds a batch with the given closure and writes it atomically.
+    ///
+    /// If the closure returns an error, nothing is written and the error is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::{DB, Options, WriteOptions};
+    ///
+    /// let path = "_path_for_rocksdb_storage_batch";
+    /// {
+    ///     let db = DB::open_default(path).unwrap();
+    ///     db.batch(&WriteOptions::default(), |batch| {
+    ///         batch.put(b"k1", b"v1");
+    ///         batch.delete(b"k2");
+    ///         Ok(())
+    ///     })
+    ///     .unwrap();
+    /// }
+    /// let _ = DB::destroy(&Options::default(), path);
+    /// ```
+    pub fn batch<F>(&self, writeopts: &WriteOptions, f: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut WriteBatch) -> Result<(), Error>,
+    {
+        let mut batch = WriteBatch::default();
+        f(&mut batch)?;
+        self.write_opt(batch, writeopts)
+    }
+
     /// Return the bytes associated with a key value with read options. If you only intend to use
     /// the vector returned temporarily, consider using [`get_pinned_opt`](#method.get_pinned_opt)
     /// to avoid unnecessary memory copy.
@@ -1035,6 +1106,56 @@ impl<T: ThreadMode> DBWithThreadMode<T> {
         )
     }
 
+    /// Opens an iterator over the keys between `start` and `end`.
+    ///
+    /// With `Direction::Forward` the keys in `[start, end)` are yielded in
+    /// ascending order. With `Direction::Reverse` the keys in `(start, end]` are
+    /// yielded in descending order, starting at `end` if it exists. An empty
+    /// range yields nothing.
+    ///
+    /// The reverse bounds are computed by appending a zero byte to `start` and
+    /// `end`, which gives the next larger key under the default byte-wise
+    /// comparator only.
+    pub fn range_iterator<'a: 'b, 'b, K: AsRef<[u8]>>(
+        &'a self,
+        start: K,
+        end: K,
+        direction: Direction,
+    ) -> DBIteratorWithThreadMode<'b, Self> {
+        let (readopts, mode) = range_read_options(start.as_ref(), end.as_ref(), direction);
+        DBIteratorWithThreadMode::new(self, readopts, mode)
+    }
+
+    /// Opens an iterator over the keys between `start` and `end` in the given
+    /// column family. See [`range_iterator`](Self::range_iterator) for details.
+    pub fn range_iterator_cf<'a: 'b, 'b, K: AsRef<[u8]>>(
+        &'a self,
+        cf_handle: &impl AsColumnFamilyRef,
+        start: K,
+        end: K,
+        direction: Direction,
+    ) -> DBIteratorWithThreadMode<'b, Self> {
+        let (readopts, mode) = range_read_options(start.as_ref(), end.as_ref(), direction);
+        DBIteratorWithThreadMode::new_cf(self, cf_handle.inner(), readopts, mode)
+    }
+
+    /// Returns the number of keys in the range `[start, end)`.
+    pub fn count_range<K: AsRef<[u8]>>(&self, start: K, end: K) -> usize {
+        self.range_iterator(start, end, Direction::Forward)
+            .count_remaining()
+    }
+
+    /// Returns the number of keys in the range `[start, end)` of the given column family.
+    pub fn count_range_cf<K: AsRef<[u8]>>(
+        &self,
+        cf_handle: &impl AsColumnFamilyRef,
+        start: K,
+        end: K,
+    ) -> usize {
+        self.range_iterator_cf(cf_handle, start, end, Direction::Forward)
+            .count_remaining()
+    }
+
     /// Opens a raw iterator over the database, using the default read options
     pub fn raw_iterator<'a: 'b, 'b>(&'a self) -> DBRawIteratorWithThreadMode<'b, Self> {
         let opts = ReadOptions::default();
@@ -1067,6 +1188,36 @@ impl<T: ThreadMode> DBWithThreadMode<T> {
         DBRawIteratorWithThreadMode::new_cf(self, cf_handle.inner(), readopts)
     }
 
+    /// Opens a raw iterator over the database which is bounded to the prefix of
+    /// `prefix`, as defined by the configured prefix extractor, and seeks to it.
+    ///
+    /// Iteration stops at the prefix boundary in both directions, and later
+    /// seeks are bounded to the prefix of their own target key.
+    pub fn prefix_raw_iterator<'a: 'b, 'b, P: AsRef<[u8]>>(
+        &'a self,
+        prefix: P,
+    ) -> DBRawIteratorWithThreadMode<'b, Self> {
+        let mut opts = ReadOptions::default();
+        opts.set_prefix_same_as_start(true);
+        let mut iter = DBRawIteratorWithThreadMode::new(self, opts);
+        iter.seek(prefix);
+        iter
+    }
+
+    /// Opens a raw iterator over the given column family which is bounded to the
+    /// prefix of `prefix` and seeks to it. See `prefix_raw_iterator` for details.
+    pub fn prefix_raw_iterator_cf<'a: 'b, 'b, P: AsRef<[u8]>>(
+        &'a self,
+        cf_handle: &impl AsColumnFamilyRef,
+        prefix: P,
+    ) -> DBRawIteratorWithThreadMode<'b, Self> {
+        let mut opts = ReadOptions::default();
+        opts.set_prefix_same_as_start(true);
+        let mut iter = DBRawIteratorWithThreadMode::new_cf(self, cf_handle.inner(), opts);
+        iter.seek(prefix);
+        iter
+    }
+
     pub fn snapshot(&self) -> SnapshotWithThreadMode<Self> {
         SnapshotWithThreadMode::<Self>::new(self)
     }
@@ -1207,6 +1358,36 @@ impl<T: ThreadMode> DBWithThreadMode<T> {
         }
     }
 
+    /// Removes the database entry for key using given write options, assuming
+    /// the key was written exactly once.
+    ///
+    /// This must only be used for keys that are never overwritten or merged,
+    /// and that are deleted at most once. Unlike `delete`, the tombstone is
+    /// dropped as soon as it meets the put it removes during compaction. If
+    /// the contract is broken the result is undefined: older versions of the
+    /// key may reappear.
+    pub fn single_delete_opt<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+        writeopts: &WriteOptions,
+    ) -> Result<(), Error> {
+        let mut batch = WriteBatch::default();
+        batch.single_delete(key);
+        self.write_opt(batch, writeopts)
+    }
+
+    /// Same as `single_delete_opt` but for a specific column family.
+    pub fn single_delete_cf_opt<K: AsRef<[u8]>>(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+        key: K,
+        writeopts: &WriteOptions,
+    ) -> Result<(), Error> {
+        let mut batch = WriteBatch::default();
+        batch.single_delete_cf(cf, key);
+        self.write_opt(batch, writeopts)
+    }
+
     /// Removes the database entries in the range `["from", "to")` using given write options.
     pub fn delete_range_cf_opt<K: AsRef<[u8]>>(
         &self,
@@ -1276,6 +1457,21 @@ impl<T: ThreadMode> DBWithThreadMode<T> {
         self.delete_cf_opt(cf, key.as_ref(), &WriteOptions::default())
     }
 
+    /// Removes the database entry for key using default write options, assuming
+    /// the key was written exactly once. See `single_delete_opt` for the contract.
+    pub fn single_delete<K: AsRef<[u8]>>(&self, key: K) -> Result<(), Error> {
+        self.single_delete_opt(key, &WriteOptions::default())
+    }
+
+    /// Same as `single_delete` but for a specific column family.
+    pub fn single_delete_cf<K: AsRef<[u8]>>(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+        key: K,
+    ) -> Result<(), Error> {
+        self.single_delete_cf_opt(cf, key, &WriteOptions::default())
+    }
+
     /// Removes the database entries in the range `["from", "to")` using default write options.
     pub fn delete_range_cf<K: AsRef<[u8]>>(
         &self,
@@ -1484,6 +1680,45 @@ impl<T: ThreadMode> DBWithThreadMode<T> {
         }
     }
 
+    /// Retrieves a RocksDB property by name into the given buffer, reusing its
+    /// allocation.
+    ///
+    /// The buffer is cleared first. Returns `false`, leaving the buffer empty,
+    /// if the property is unknown.
+    pub fn property_value_into(&self, name: &str, buf: &mut String) -> Result<bool, Error> {
+        let prop_name = match CString::new(name) {
+            Ok(c) => c,
+            Err(e) => {
+                return Err(Error::new(format!(
+                    "Failed to convert property name to CString: {}",
+                    e
+                )));
+            }
+        };
+
+        buf.clear();
+        unsafe {
+            let value = ffi::rocksdb_property_value(self.inner, prop_name.as_ptr());
+            if value.is_null() {
+                return Ok(false);
+            }
+
+            let result = match CStr::from_ptr(value).to_str() {
+                Ok(s) => {
+                    buf.push_str(s);
+                    Ok(true)
+                }
+                Err(e) => Err(Error::new(format!(
+                    "Failed to convert property value to string: {}",
+                    e
+                ))),
+            };
+
+            libc::free(value as *mut c_void);
+            result
+        }
+    }
+
     /// Retrieves a RocksDB property and casts it to an integer.
     ///
     /// Full list of properties that return int values could be find
@@ -1524,6 +1759,63 @@ impl<T: ThreadMode> DBWithThreadMode<T> {
         }
     }
 
+    /// Returns the number of files at the given level, as reported by the
+    /// `rocksdb.num-files-at-level<N>` property.
+    pub fn num_files_at_level(&self, level: usize) -> Result<u64, Error> {
+        let name = format!("rocksdb.num-files-at-level{}", level);
+        self.property_int_value(&name)?
+            .ok_or_else(|| Error::new(format!("Property {} is not available", name)))
+    }
+
+    /// Returns the number of files at the given level of a specific column family,
+    /// as reported by the `rocksdb.num-files-at-level<N>` property.
+    pub fn num_files_at_level_cf(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+        level: usize,
+    ) -> Result<u64, Error> {
+        let name = format!("rocksdb.num-files-at-level{}", level);
+        self.property_int_value_cf(cf, &name)?
+            .ok_or_else(|| Error::new(format!("Property {} is not available", name)))
+    }
+
+    /// Returns a snapshot of commonly monitored integer properties of the
+    /// default column family.
+    ///
+    /// Properties that are not available are reported as `None`.
+    pub fn live_stats(&self) -> Result<LiveStats, Error> {
+        Ok(LiveStats {
+            estimate_num_keys: self.property_int_value("rocksdb.estimate-num-keys")?,
+            num_immutable_mem_table: self.property_int_value("rocksdb.num-immutable-mem-table")?,
+            cur_size_active_mem_table: self
+                .property_int_value("rocksdb.cur-size-active-mem-table")?,
+            cur_size_all_mem_tables: self.property_int_value("rocksdb.cur-size-all-mem-tables")?,
+            estimate_table_readers_mem: self
+                .property_int_value("rocksdb.estimate-table-readers-mem")?,
+            total_sst_files_size: self.property_int_value("rocksdb.total-sst-files-size")?,
+        })
+    }
+
+    fn aggregated_int_property_internal<C: AsColumnFamilyRef>(
+        &self,
+        name: &str,
+        cfs: impl IntoIterator<Item = C>,
+    ) -> Result<Option<u64>, Error> {
+        // The default column family always exists, but is only tracked in the column family
+        // map when the database was opened with column families.
+        let mut total = match self.property_int_value(name)? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        for cf in cfs {
+            match self.property_int_value_cf(&cf, name)? {
+                Some(value) => total += value,
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(total))
+    }
+
     /// The sequence number of the most recent transaction.
     pub fn latest_sequence_number(&self) -> u64 {
         unsafe { ffi::rocksdb_get_latest_sequence_number(self.inner) }
@@ -1609,6 +1901,67 @@ impl<T: ThreadMode> DBWithThreadMode<T> {
         self.ingest_external_file_raw_cf(cf, &opts, &paths_v, &cpaths)
     }
 
+    /// Writes the given key-value pairs into a temporary SST file and ingests it.
+    ///
+    /// The items must be sorted by key according to the comparator in `opts`,
+    /// otherwise the error reported by the SST file writer is returned. The
+    /// temporary file is created in the database directory and moved into the
+    /// database rather than copied. It is removed on every error path, including
+    /// a panic while producing `items`.
+    pub fn bulk_ingest<K, V, I>(&self, items: I, opts: &Options) -> Result<(), Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        static BULK_INGEST_ID: AtomicUsize = AtomicUsize::new(0);
+
+        struct RemoveOnDrop(PathBuf);
+
+        impl Drop for RemoveOnDrop {
+            fn drop(&mut self) {
+                // after a successful move the file is already gone
+                let _ = fs::remove_file(&self.0);
+            }
+        }
+
+        let sst_path = RemoveOnDrop(self.path.join(format!(
+            "bulk_ingest_{}_{}.sst",
+            std::process::id(),
+            BULK_INGEST_ID.fetch_add(1, Ordering::SeqCst)
+        )));
+        self.bulk_ingest_raw(items, opts, &sst_path.0)
+    }
+
+    fn bulk_ingest_raw<K, V, I>(
+        &self,
+        items: I,
+        opts: &Options,
+        sst_path: &Path,
+    ) -> Result<(), Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut writer = SstFileWriter::create(opts);
+        writer.open(sst_path)?;
+        let mut count = 0;
+        for (key, value) in items {
+            writer.put(key, value)?;
+            count += 1;
+        }
+        if count == 0 {
+            // an SST file without entries cannot be finished
+            return Ok(());
+        }
+        writer.finish()?;
+
+        let mut ingest_opts = IngestExternalFileOptions::default();
+        ingest_opts.set_move_files(true);
+        self.ingest_external_file_opts(&ingest_opts, vec![sst_path])
+    }
+
     fn ingest_external_file_raw(
         &self,
         opts: &IngestExternalFileOptions,
@@ -1698,6 +2051,10 @@ impl<T: ThreadMode> DBWithThreadMode<T> {
     ///
     /// Note: L0 files are left regardless of whether they're in the range.
     ///
+    /// Unlike `delete_range`, no tombstones are written: files are physically
+    /// removed, and files which only partially overlap the range are left
+    /// alone.
+    ///
     /// SnapshotWithThreadModes before the delete might not see the data in the given range.
     pub fn delete_file_in_range<K: AsRef<[u8]>>(&self, from: K, to: K) -> Result<(), Error> {
         let from = from.as_ref();
@@ -1743,6 +2100,46 @@ impl<T: ThreadMode> DBWithThreadMode<T> {
         }
     }
 
+    /// Prevent file deletions. Compactions will continue to occur, but no
+    /// obsolete files will be deleted. Calling this multiple times have the
+    /// same effect as calling it once.
+    pub fn disable_file_deletions(&self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_disable_file_deletions(self.inner));
+        }
+        Ok(())
+    }
+
+    /// Allow compactions to delete obsolete files.
+    ///
+    /// If `force` is true, file deletions are re-enabled regardless of how many
+    /// times `disable_file_deletions` has been called. Otherwise, deletions are
+    /// only re-enabled once this has been called as many times as
+    /// `disable_file_deletions`, which allows nested disable/enable pairs.
+    pub fn enable_file_deletions(&self, force: bool) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_enable_file_deletions(
+                self.inner,
+                force as c_uchar
+            ));
+        }
+        Ok(())
+    }
+
+    /// Flushes the default column family and disables file deletions, returning
+    /// a guard that re-enables them when dropped.
+    ///
+    /// While the guard is alive, the files listed by [`FileCopyGuard::live_files`]
+    /// will not be removed by compactions, so they can be safely copied out of
+    /// the database directory, e.g. for a backup. Other column families should
+    /// be flushed with [`flush_cf`](Self::flush_cf) beforehand if their memtable
+    /// contents need to be part of the copy.
+    pub fn prepare_for_file_copy(&self) -> Result<FileCopyGuard<T>, Error> {
+        self.flush()?;
+        self.disable_file_deletions()?;
+        Ok(FileCopyGuard { db: self })
+    }
+
     fn drop_column_family<C>(
         &self,
         cf_inner: *mut ffi::rocksdb_column_family_handle_t,
@@ -1782,6 +2179,20 @@ impl DBWithThreadMode<SingleThreaded> {
     pub fn cf_handle(&self, name: &str) -> Option<&ColumnFamily> {
         self.cfs.cfs.get(name)
     }
+
+    /// Retrieves a RocksDB integer property summed over all column families,
+    /// e.g. `rocksdb.size-all-mem-tables` for the whole database.
+    ///
+    /// Returns `Ok(None)` if the property is not available.
+    pub fn aggregated_int_property(&self, name: &str) -> Result<Option<u64>, Error> {
+        let cfs = self
+            .cfs
+            .cfs
+            .iter()
+            .filter(|(cf_name, _)| *cf_name != DEFAULT_COLUMN_FAMILY_NAME)
+            .map(|(_, cf)| cf);
+        self.aggregated_int_property_internal(name, cfs)
+    }
 }
 
 impl DBWithThreadMode<MultiThreaded> {
@@ -1815,6 +2226,23 @@ impl DBWithThreadMode<MultiThreaded> {
             .cloned()
             .map(UnboundColumnFamily::bound_column_family)
     }
+
+    /// Retrieves a RocksDB integer property summed over all column families,
+    /// e.g. `rocksdb.size-all-mem-tables` for the whole database.
+    ///
+    /// Returns `Ok(None)` if the property is not available.
+    pub fn aggregated_int_property(&self, name: &str) -> Result<Option<u64>, Error> {
+        let cfs: Vec<_> = self
+            .cfs
+            .cfs
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(cf_name, _)| *cf_name != DEFAULT_COLUMN_FAMILY_NAME)
+            .map(|(_, cf)| cf.clone().bound_column_family())
+            .collect();
+        self.aggregated_int_property_internal(name, cfs)
+    }
 }
 
 impl<T: ThreadMode> Drop for DBWithThreadMode<T> {
@@ -1832,6 +2260,23 @@ impl<T: ThreadMode> fmt::Debug for DBWithThreadMode<T> {
     }
 }
 
+/// Commonly monitored integer properties, as returned by `DB::live_stats`
+#[derive(Debug, Clone, Default)]
+pub struct LiveStats {
+    /// Estimated number of keys (`rocksdb.estimate-num-keys`)
+    pub estimate_num_keys: Option<u64>,
+    /// Number of immutable memtables not yet flushed (`rocksdb.num-immutable-mem-table`)
+    pub num_immutable_mem_table: Option<u64>,
+    /// Approximate size of the active memtable in bytes (`rocksdb.cur-size-active-mem-table`)
+    pub cur_size_active_mem_table: Option<u64>,
+    /// Approximate size of all memtables in bytes (`rocksdb.cur-size-all-mem-tables`)
+    pub cur_size_all_mem_tables: Option<u64>,
+    /// Estimated memory used by table readers (`rocksdb.estimate-table-readers-mem`)
+    pub estimate_table_readers_mem: Option<u64>,
+    /// Total size of all SST files in bytes (`rocksdb.total-sst-files-size`)
+    pub total_sst_files_size: Option<u64>,
+}
+
 /// The metadata that describes a SST file
 #[derive(Debug, Clone)]
 pub struct LiveFile {
@@ -1851,6 +2296,53 @@ pub struct LiveFile {
     pub num_deletions: u64,
 }
 
+/// Keeps file deletions disabled for as long as it is alive.
+///
+/// Created by [`DBWithThreadMode::prepare_for_file_copy`]. File deletions are
+/// re-enabled when the guard is dropped.
+pub struct FileCopyGuard<'a, T: ThreadMode> {
+    db: &'a DBWithThreadMode<T>,
+}
+
+impl<'a, T: ThreadMode> FileCopyGuard<'a, T> {
+    /// Returns the live SST files of the database.
+    ///
+    /// Note that this only lists table files. A usable copy of the database
+    /// also needs its `CURRENT`, `MANIFEST-*` and `OPTIONS-*` files.
+    pub fn live_files(&self) -> Result<Vec<LiveFile>, Error> {
+        self.db.live_files()
+    }
+}
+
+impl<'a, T: ThreadMode> Drop for FileCopyGuard<'a, T> {
+    fn drop(&mut self) {
+        let _ = self.db.enable_file_deletions(false);
+    }
+}
+
+fn range_read_options(
+    start: &[u8],
+    end: &[u8],
+    direction: Direction,
+) -> (ReadOptions, IteratorMode<'static>) {
+    let mut readopts = ReadOptions::default();
+    let mode = match direction {
+        Direction::Forward => {
+            // [start, end)
+            readopts.set_iterate_lower_bound(start);
+            readopts.set_iterate_upper_bound(end);
+            IteratorMode::Start
+        }
+        Direction::Reverse => {
+            // (start, end]: appending a zero byte yields the immediate successor
+            readopts.set_iterate_lower_bound([start, &[0u8][..]].concat());
+            readopts.set_iterate_upper_bound([end, &[0u8][..]].concat());
+            IteratorMode::End
+        }
+    };
+    (readopts, mode)
+}
+
 fn convert_options(opts: &[(&str, &str)]) -> Result<Vec<(CString, CString)>, Error> {
     opts.iter()
         .map(|(name, value)| {
@@ -72,9 +72,10 @@ pub type DBRawIterator<'a> = DBRawIteratorWithThreadMode<'a, DB>;
 pub struct DBRawIteratorWithThreadMode<'a, D: DBAccess> {
     inner: *mut ffi::rocksdb_iterator_t,
 
-    /// When iterate_upper_bound is set, the inner C iterator keeps a pointer to the upper bound
-    /// inside `_readopts`. Storing this makes sure the upper bound is always alive when the
-    /// iterator is being used.
+    /// When iterate_upper_bound or iterate_lower_bound is set, the inner C iterator keeps a
+    /// pointer to the bound inside `_readopts`. Storing this makes sure the bounds are always
+    /// alive when the iterator is being used, so the read options are taken by value rather than
+    /// borrowed.
     _readopts: ReadOptions,
 
     db: PhantomData<&'a D>,
@@ -369,6 +370,11 @@ pub type DBIterator<'a> = DBIteratorWithThreadMode<'a, DB>;
 ///     for (key, value) in iter {
 ///         println!("Saw {:?} {:?}", key, value);
 ///     }
+///
+///     iter = db.iterator(IteratorMode::Prefix(b"my")); // Only keys starting with "my"
+///     for (key, value) in iter {
+///         println!("Saw {:?} {:?}", key, value);
+///     }
 /// }
 /// let _ = DB::destroy(&Options::default(), path);
 /// ```
@@ -376,6 +382,9 @@ pub struct DBIteratorWithThreadMode<'a, D: DBAccess> {
     raw: DBRawIteratorWithThreadMode<'a, D>,
     direction: Direction,
     just_seeked: bool,
+    prefix: Option<Box<[u8]>>,
+    /// Set once iteration has left `prefix`, cleared by `set_mode`.
+    done: bool,
 }
 
 pub enum Direction {
@@ -386,9 +395,25 @@ pub enum Direction {
 pub type KVBytes = (Box<[u8]>, Box<[u8]>);
 
 pub enum IteratorMode<'a> {
+    /// Iterate forward, starting from the first key.
     Start,
+    /// Iterate backward, starting from the last key.
     End,
+    /// Iterate in the given direction, starting from the given key.
+    ///
+    /// With `Direction::Forward` iteration starts at the first key greater
+    /// than or equal to the given one (see
+    /// [`seek`](DBRawIteratorWithThreadMode::seek)). With `Direction::Reverse`
+    /// it starts at the last key less than or equal to the given one (see
+    /// [`seek_for_prev`](DBRawIteratorWithThreadMode::seek_for_prev)).
     From(&'a [u8], Direction),
+    /// Iterate forward over the keys starting with the given prefix.
+    ///
+    /// Iteration starts at the first key greater than or equal to the prefix
+    /// and ends at the first key that does not start with it; the iterator
+    /// stays exhausted after that. Unlike `prefix_iterator`, this does not
+    /// require a prefix extractor.
+    Prefix(&'a [u8]),
 }
 
 impl<'a, D: DBAccess> DBIteratorWithThreadMode<'a, D> {
@@ -397,6 +422,8 @@ impl<'a, D: DBAccess> DBIteratorWithThreadMode<'a, D> {
             raw: DBRawIteratorWithThreadMode::new(db, readopts),
             direction: Direction::Forward, // blown away by set_mode()
             just_seeked: false,
+            prefix: None,
+            done: false,
         };
         rv.set_mode(mode);
         rv
@@ -412,12 +439,16 @@ impl<'a, D: DBAccess> DBIteratorWithThreadMode<'a, D> {
             raw: DBRawIteratorWithThreadMode::new_cf(db, cf_handle, readopts),
             direction: Direction::Forward, // blown away by set_mode()
             just_seeked: false,
+            prefix: None,
+            done: false,
         };
         rv.set_mode(mode);
         rv
     }
 
     pub fn set_mode(&mut self, mode: IteratorMode) {
+        self.prefix = None;
+        self.done = false;
         match mode {
             IteratorMode::Start => {
                 self.raw.seek_to_first();
@@ -435,28 +466,71 @@ impl<'a, D: DBAccess> DBIteratorWithThreadMode<'a, D> {
                 self.raw.seek_for_prev(key);
                 self.direction = Direction::Reverse;
             }
+            IteratorMode::Prefix(prefix) => {
+                self.raw.seek(prefix);
+                self.direction = Direction::Forward;
+                self.prefix = Some(Box::from(prefix));
+            }
         };
 
         self.just_seeked = true;
     }
 
     /// See [`valid`](DBRawIteratorWithThreadMode::valid)
+    ///
+    /// With `IteratorMode::Prefix` this also returns `false` once iteration
+    /// has moved past the last key with the prefix.
     pub fn valid(&self) -> bool {
-        self.raw.valid()
+        !self.done && self.raw.valid()
     }
 
     /// See [`status`](DBRawIteratorWithThreadMode::status)
     pub fn status(&self) -> Result<(), Error> {
         self.raw.status()
     }
-}
 
-impl<'a, D: DBAccess> Iterator for DBIteratorWithThreadMode<'a, D> {
-    type Item = KVBytes;
+    /// Calls `f` with the key and value of each remaining entry, borrowing them
+    /// from the underlying iterator instead of allocating a copy per entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::{DB, IteratorMode, Options};
+    ///
+    /// let path = "_path_for_rocksdb_storage_for_each_pinned";
+    /// {
+    ///     let db = DB::open_default(path).unwrap();
+    ///     db.put(b"k1", b"value").unwrap();
+    ///     let mut total = 0;
+    ///     db.iterator(IteratorMode::Start)
+    ///         .for_each_pinned(|_key, value| total += value.len());
+    ///     assert_eq!(total, 5);
+    /// }
+    /// let _ = DB::destroy(&Options::default(), path);
+    /// ```
+    pub fn for_each_pinned(&mut self, mut f: impl FnMut(&[u8], &[u8])) {
+        while self.advance() {
+            // .key() and .value() only ever return None if valid == false, which advance() checks
+            f(self.raw.key().unwrap(), self.raw.value().unwrap());
+        }
+    }
 
-    fn next(&mut self) -> Option<KVBytes> {
-        if !self.raw.valid() {
-            return None;
+    /// Consumes the iterator and returns the number of remaining entries.
+    ///
+    /// Unlike `Iterator::count`, this does not copy every key and value.
+    pub fn count_remaining(mut self) -> usize {
+        let mut count = 0;
+        while self.advance() {
+            count += 1;
+        }
+        count
+    }
+
+    /// Moves to the next entry in the iteration direction, returning whether
+    /// the iterator is positioned on an entry that should be yielded.
+    fn advance(&mut self) -> bool {
+        if self.done || !self.raw.valid() {
+            return false;
         }
 
         // Initial call to next() after seeking should not move the iterator
@@ -470,8 +544,25 @@ impl<'a, D: DBAccess> Iterator for DBIteratorWithThreadMode<'a, D> {
             }
         }
 
-        if self.raw.valid() {
-            // .key() and .value() only ever return None if valid == false, which we've just checked
+        match (self.raw.key(), &self.prefix) {
+            (Some(key), Some(prefix)) => {
+                // Stay exhausted even if a later key has the prefix again,
+                // which a custom comparator allows
+                self.done = !key.starts_with(prefix);
+                !self.done
+            }
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
+
+impl<'a, D: DBAccess> Iterator for DBIteratorWithThreadMode<'a, D> {
+    type Item = KVBytes;
+
+    fn next(&mut self) -> Option<KVBytes> {
+        if self.advance() {
+            // .key() and .value() only ever return None if valid == false, which advance() checks
             Some((
                 Box::from(self.raw.key().unwrap()),
                 Box::from(self.raw.value().unwrap()),
@@ -263,8 +263,7 @@ impl BlockBasedOptionsMustOutliveDB {
 ///    opts.set_level_zero_stop_writes_trigger(2000);
 ///    opts.set_level_zero_slowdown_writes_trigger(0);
 ///    opts.set_compaction_style(DBCompactionStyle::Universal);
-///    opts.set_max_background_compactions(4);
-///    opts.set_max_background_flushes(4);
+///    opts.set_max_background_jobs(8);
 ///    opts.set_disable_auto_compactions(true);
 ///
 ///    DB::open(&opts, path).unwrap()
@@ -273,6 +272,12 @@ impl BlockBasedOptionsMustOutliveDB {
 pub struct Options {
     pub(crate) inner: *mut ffi::rocksdb_options_t,
     pub(crate) outlive: OptionsMustOutliveDB,
+    // RocksDB does not expose getters for these, so the last values set are
+    // cached here for `validate` and the Rust-side getters.
+    max_write_buffer_number: Option<c_int>,
+    min_write_buffer_number_to_merge: Option<c_int>,
+    table_cache_num_shard_bits: Option<c_int>,
+    prefer_unified_background_jobs: bool,
 }
 
 /// Optionally disable WAL or sync for this write.
@@ -413,6 +418,10 @@ impl Clone for Options {
         Self {
             inner,
             outlive: self.outlive.clone(),
+            max_write_buffer_number: self.max_write_buffer_number,
+            min_write_buffer_number_to_merge: self.min_write_buffer_number_to_merge,
+            table_cache_num_shard_bits: self.table_cache_num_shard_bits,
+            prefer_unified_background_jobs: self.prefer_unified_background_jobs,
         }
     }
 }
@@ -559,7 +568,26 @@ impl BlockBasedOptions {
         }
     }
 
-    /// Sets the filter policy to reduce disk reads
+    /// Sets the filter policy to reduce disk reads.
+    ///
+    /// Installs a Bloom filter using `bits_per_key` bits per key. If
+    /// `block_based` is true, the deprecated block-based filter format is
+    /// used, otherwise a full filter is built for each SST file.
+    ///
+    /// The filter policy is owned by these options and lives as long as the
+    /// table factory created from them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::{BlockBasedOptions, Options};
+    ///
+    /// let mut block_opts = BlockBasedOptions::default();
+    /// block_opts.set_bloom_filter(10, false);
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_block_based_table_factory(&block_opts);
+    /// ```
     pub fn set_bloom_filter(&mut self, bits_per_key: c_int, block_based: bool) {
         unsafe {
             let bloom = if block_based {
@@ -815,6 +843,8 @@ impl Options {
                 memtable_memory_budget as u64,
             );
         }
+        self.min_write_buffer_number_to_merge = Some(2);
+        self.max_write_buffer_number = Some(6);
     }
 
     /// Optimize universal style compaction.
@@ -840,6 +870,8 @@ impl Options {
                 memtable_memory_budget as u64,
             );
         }
+        self.min_write_buffer_number_to_merge = Some(2);
+        self.max_write_buffer_number = Some(6);
     }
 
     /// If true, the database will be created if it is missing.
@@ -902,7 +934,7 @@ impl Options {
     /// the database will switch to read-only mode and fail all other
     /// Write operations.
     ///
-    /// Default: false
+    /// Default: true
     pub fn set_paranoid_checks(&mut self, enabled: bool) {
         unsafe {
             ffi::rocksdb_options_set_paranoid_checks(self.inner, enabled as c_uchar);
@@ -1094,6 +1126,61 @@ impl Options {
         }
     }
 
+    /// Files older than this value will be picked up for compaction, and
+    /// re-written to the same level as they were before. This gives compaction
+    /// filters a chance to run over data that is otherwise never rewritten.
+    ///
+    /// Only supported in level and universal compaction styles. A value of `0`
+    /// disables the feature.
+    ///
+    /// Default: 0 (disabled)
+    ///
+    /// Dynamically changeable through SetOptions() API
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_periodic_compaction_seconds(60 * 60 * 24 * 7); // one week
+    /// ```
+    pub fn set_periodic_compaction_seconds(&mut self, secs: u64) {
+        unsafe {
+            ffi::rocksdb_options_set_periodic_compaction_seconds(self.inner, secs);
+        }
+    }
+
+    /// Files whose keys are all older than this value will be compacted to the
+    /// next level (level compaction) or deleted (FIFO compaction).
+    ///
+    /// A value of `0` disables the feature.
+    ///
+    /// Default: 0 (disabled)
+    ///
+    /// Dynamically changeable through SetOptions() API
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_ttl(60 * 60 * 24 * 30); // thirty days
+    /// ```
+    pub fn set_ttl(&mut self, secs: u64) {
+        unsafe {
+            ffi::rocksdb_options_set_ttl(self.inner, secs);
+        }
+    }
+
+    /// Sets a merge operator whose full and partial merges use the same
+    /// function.
+    ///
+    /// If `full_merge_fn` panics, the panic is caught and treated as if it had
+    /// returned `None`. A failed full merge surfaces to the caller of the read
+    /// or compaction that ran it as an [`Error`] with a `Corruption` status
+    /// from RocksDB; a failed partial merge only leaves the operands unmerged.
     pub fn set_merge_operator_associative<F: MergeFn + Clone>(
         &mut self,
         name: &str,
@@ -1118,6 +1205,12 @@ impl Options {
         }
     }
 
+    /// Sets a merge operator with separate full and partial merge functions.
+    ///
+    /// If either function panics, the panic is caught and treated as if it had
+    /// returned `None`. A failed full merge surfaces to the caller of the read
+    /// or compaction that ran it as an [`Error`] with a `Corruption` status
+    /// from RocksDB; a failed partial merge only leaves the operands unmerged.
     pub fn set_merge_operator<F: MergeFn, PF: MergeFn>(
         &mut self,
         name: &str,
@@ -1213,6 +1306,9 @@ impl Options {
     /// The client must ensure that the comparator supplied here has the same
     /// name and orders keys *exactly* the same as the comparator provided to
     /// previous open calls on the same DB.
+    ///
+    /// If `compare_fn` panics, the process is aborted: no ordering returned in
+    /// its place could keep the keys already stored in a consistent order.
     pub fn set_comparator(&mut self, name: &str, compare_fn: CompareFn) {
         let cb = Box::new(ComparatorCallback {
             name: CString::new(name.as_bytes()).unwrap(),
@@ -1230,6 +1326,20 @@ impl Options {
         }
     }
 
+    /// Sets the prefix extractor used for prefix blooms and prefix seeks.
+    ///
+    /// RocksDB takes ownership of the transform, which stays alive for as long
+    /// as any options or database still refer to it, so the `Options` may be
+    /// dropped after opening the database.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::{Options, SliceTransform};
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_prefix_extractor(SliceTransform::create_fixed_prefix(3));
+    /// ```
     pub fn set_prefix_extractor(&mut self, prefix_extractor: SliceTransform) {
         unsafe { ffi::rocksdb_options_set_prefix_extractor(self.inner, prefix_extractor.inner) }
     }
@@ -1587,6 +1697,12 @@ impl Options {
         unsafe {
             ffi::rocksdb_options_set_table_cache_numshardbits(self.inner, nbits);
         }
+        self.table_cache_num_shard_bits = Some(nbits);
+    }
+
+    /// Returns the value last passed to `set_table_cache_num_shard_bits`, if any.
+    pub fn table_cache_num_shard_bits(&self) -> Option<c_int> {
+        self.table_cache_num_shard_bits
     }
 
     /// By default target_file_size_multiplier is 1, which means
@@ -1621,6 +1737,7 @@ impl Options {
         unsafe {
             ffi::rocksdb_options_set_min_write_buffer_number_to_merge(self.inner, nbuf);
         }
+        self.min_write_buffer_number_to_merge = Some(nbuf);
     }
 
     /// Sets the maximum number of write buffers that are built up in memory.
@@ -1645,6 +1762,47 @@ impl Options {
         unsafe {
             ffi::rocksdb_options_set_max_write_buffer_number(self.inner, nbuf);
         }
+        self.max_write_buffer_number = Some(nbuf);
+    }
+
+    /// Returns the value last passed to `set_max_write_buffer_number`, or the
+    /// value set by `optimize_level_style_compaction` and
+    /// `optimize_universal_style_compaction`, if either was called later.
+    ///
+    /// This only reflects calls made through this `Options`; `None` means
+    /// RocksDB's default is in effect.
+    pub fn max_write_buffer_number(&self) -> Option<c_int> {
+        self.max_write_buffer_number
+    }
+
+    /// Checks the options for inconsistent combinations that RocksDB would
+    /// otherwise silently adjust or that would stall writes.
+    ///
+    /// Currently this flags a `min_write_buffer_number_to_merge` that is not
+    /// smaller than `max_write_buffer_number`, in which case the memtables
+    /// can never be merged before writes stall.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_max_write_buffer_number(2);
+    /// opts.set_min_write_buffer_number_to_merge(2);
+    /// assert!(opts.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), Error> {
+        let max_number = self.max_write_buffer_number.unwrap_or(2);
+        let min_to_merge = self.min_write_buffer_number_to_merge.unwrap_or(1);
+        if min_to_merge >= max_number {
+            return Err(Error::new(format!(
+                "min_write_buffer_number_to_merge ({}) must be smaller than \
+                 max_write_buffer_number ({})",
+                min_to_merge, max_number
+            )));
+        }
+        Ok(())
     }
 
     /// Sets the amount of data to build up in memory (backed by an unsorted log
@@ -1813,6 +1971,7 @@ impl Options {
         unsafe {
             ffi::rocksdb_options_set_min_write_buffer_number_to_merge(self.inner, to_merge);
         }
+        self.min_write_buffer_number_to_merge = Some(to_merge);
     }
 
     /// Sets the number of files to trigger level-0 compaction. A value < `0` means that
@@ -1933,6 +2092,11 @@ impl Options {
     /// number) that will change the state visible to the snapshot after they are
     /// landed to the memtable.
     ///
+    /// Unordered writes require `allow_concurrent_memtable_write` to be true
+    /// (the default) and are incompatible with `enable_pipelined_write` and
+    /// with `max_successive_merges` greater than 0; opening a DB with such a
+    /// combination fails.
+    ///
     /// Default: false
     pub fn set_unordered_write(&mut self, unordered: bool) {
         unsafe {
@@ -1957,6 +2121,15 @@ impl Options {
     /// Default: 2
     ///
     /// Dynamically changeable through SetDBOptions() API.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_max_background_jobs(6);
+    /// ```
     pub fn set_max_background_jobs(&mut self, jobs: c_int) {
         unsafe {
             ffi::rocksdb_options_set_max_background_jobs(self.inner, jobs);
@@ -1989,6 +2162,9 @@ impl Options {
         note = "RocksDB automatically decides this based on the value of max_background_jobs"
     )]
     pub fn set_max_background_compactions(&mut self, n: c_int) {
+        if self.prefer_unified_background_jobs {
+            return;
+        }
         unsafe {
             ffi::rocksdb_options_set_max_background_compactions(self.inner, n);
         }
@@ -2024,11 +2200,42 @@ impl Options {
         note = "RocksDB automatically decides this based on the value of max_background_jobs"
     )]
     pub fn set_max_background_flushes(&mut self, n: c_int) {
+        if self.prefer_unified_background_jobs {
+            return;
+        }
         unsafe {
             ffi::rocksdb_options_set_max_background_flushes(self.inner, n);
         }
     }
 
+    /// Makes `max_background_jobs` the only knob controlling background work.
+    ///
+    /// When enabled, any previously set `max_background_compactions` and
+    /// `max_background_flushes` are reset so that RocksDB derives them from
+    /// `max_background_jobs`, and later calls to those deprecated setters are
+    /// ignored.
+    ///
+    /// Default: false
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.prefer_unified_background_jobs(true);
+    /// opts.set_max_background_jobs(6);
+    /// ```
+    pub fn prefer_unified_background_jobs(&mut self, enabled: bool) {
+        self.prefer_unified_background_jobs = enabled;
+        if enabled {
+            unsafe {
+                ffi::rocksdb_options_set_max_background_compactions(self.inner, -1);
+                ffi::rocksdb_options_set_max_background_flushes(self.inner, -1);
+            }
+        }
+    }
+
     /// Disables automatic compactions. Manual compactions can still
     /// be issued on this column family
     ///
@@ -2153,6 +2360,31 @@ impl Options {
         }
     }
 
+    /// Set this option to true during creation of the database if you want
+    /// to be able to ingest behind (call IngestExternalFile() skipping keys
+    /// that already exist, rather than overwriting matching keys).
+    /// Setting this option to true will affect two things:
+    /// 1) Disable some internal optimizations around SST file compression.
+    /// 2) Reserve the bottom-most level for ingested files only.
+    ///
+    /// Note that the num_levels should be >= 3 if this option is turned on.
+    ///
+    /// Default: false
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_allow_ingest_behind(true);
+    /// ```
+    pub fn set_allow_ingest_behind(&mut self, val: bool) {
+        unsafe {
+            ffi::rocksdb_options_set_allow_ingest_behind(self.inner, val as c_uchar);
+        }
+    }
+
     /// The total maximum size(bytes) of write buffers to maintain in memory
     /// including copies of buffers that have already been flushed. This parameter
     /// only affects trimming of flushed buffers and does not affect flushing.
@@ -2187,6 +2419,32 @@ impl Options {
         unsafe { ffi::rocksdb_options_set_max_write_buffer_size_to_maintain(self.inner, size) }
     }
 
+    /// The total maximum number of write buffers to maintain in memory including
+    /// copies of buffers that have already been flushed. Unlike
+    /// max_write_buffer_number, this parameter does not affect flushing.
+    /// Keeping a few flushed memtables around can reduce read amplification
+    /// for reads served from older snapshots.
+    ///
+    /// This option is deprecated in RocksDB in favor of
+    /// `set_max_write_buffer_size_to_maintain`, which takes precedence when
+    /// set to a non-zero value.
+    ///
+    /// Default: 0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_max_write_buffer_number_to_maintain(2);
+    /// ```
+    pub fn set_max_write_buffer_number_to_maintain(&mut self, nbuf: c_int) {
+        unsafe {
+            ffi::rocksdb_options_set_max_write_buffer_number_to_maintain(self.inner, nbuf);
+        }
+    }
+
     /// By default, a single write thread queue is maintained. The thread gets
     /// to the head of the queue becomes write batch group leader and responsible
     /// for writing to WAL and memtable for the batch group.
@@ -2862,6 +3120,75 @@ impl Options {
             );
         }
     }
+
+    /// Enable the use of key-value separation.
+    ///
+    /// More details can be found here: http://rocksdb.org/blog/2021/05/26/integrated-blob-db.html.
+    ///
+    /// Default: false (disable)
+    ///
+    /// Dynamically changeable through SetOptions() API
+    pub fn set_enable_blob_files(&mut self, val: bool) {
+        unsafe {
+            ffi::rocksdb_options_set_enable_blob_files(self.inner, val as c_uchar);
+        }
+    }
+
+    /// Sets the minimum threshold value at or above which will be written
+    /// to blob files during flush or compaction.
+    ///
+    /// Dynamically changeable through SetOptions() API
+    pub fn set_min_blob_size(&mut self, val: u64) {
+        unsafe {
+            ffi::rocksdb_options_set_min_blob_size(self.inner, val);
+        }
+    }
+
+    /// Sets the size limit for blob files.
+    ///
+    /// Dynamically changeable through SetOptions() API
+    pub fn set_blob_file_size(&mut self, val: u64) {
+        unsafe {
+            ffi::rocksdb_options_set_blob_file_size(self.inner, val);
+        }
+    }
+
+    /// Sets the blob compression type. All blob files use the same
+    /// compression type.
+    ///
+    /// Dynamically changeable through SetOptions() API
+    pub fn set_blob_compression_type(&mut self, val: DBCompressionType) {
+        unsafe {
+            ffi::rocksdb_options_set_blob_compression_type(self.inner, val as c_int);
+        }
+    }
+
+    /// If this is set to true RocksDB will actively relocate valid blobs from the oldest blob files
+    /// as they are encountered during compaction.
+    ///
+    /// Default: false
+    ///
+    /// Dynamically changeable through SetOptions() API
+    pub fn set_enable_blob_gc(&mut self, val: bool) {
+        unsafe {
+            ffi::rocksdb_options_set_enable_blob_gc(self.inner, val as c_uchar);
+        }
+    }
+
+    /// Sets the threshold that the GC logic uses to determine which blob files should be considered "old".
+    ///
+    /// For example, the default value of 0.25 signals to RocksDB that blobs residing in the
+    /// oldest 25% of blob files should be relocated by GC. This parameter can be tuned to adjust
+    /// the trade-off between write amplification and space amplification.
+    ///
+    /// Default: 0.25
+    ///
+    /// Dynamically changeable through SetOptions() API
+    pub fn set_blob_gc_age_cutoff(&mut self, val: f64) {
+        unsafe {
+            ffi::rocksdb_options_set_blob_gc_age_cutoff(self.inner, val);
+        }
+    }
 }
 
 impl Default for Options {
@@ -2874,6 +3201,10 @@ impl Default for Options {
             Self {
                 inner: opts,
                 outlive: OptionsMustOutliveDB::default(),
+                max_write_buffer_number: None,
+                min_write_buffer_number_to_merge: None,
+                table_cache_num_shard_bits: None,
+                prefer_unified_background_jobs: false,
             }
         }
     }
@@ -3100,6 +3431,39 @@ impl ReadOptions {
     /// If true when calling Get(), we also skip prefix bloom when reading from
     /// block based table. It provides a way to read existing data after
     /// changing implementation of prefix extractor.
+    ///
+    /// When a prefix extractor is configured together with a prefix-based
+    /// memtable (e.g. `MemtableFactory::HashSkipList`), iterators only
+    /// traverse the whole key space if this is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::{DB, IteratorMode, MemtableFactory, Options, ReadOptions, SliceTransform};
+    ///
+    /// let path = "_path_for_rocksdb_storage_total_order_seek";
+    /// {
+    ///     let mut opts = Options::default();
+    ///     opts.create_if_missing(true);
+    ///     opts.set_prefix_extractor(SliceTransform::create_fixed_prefix(3));
+    ///     opts.set_allow_concurrent_memtable_write(false);
+    ///     opts.set_memtable_factory(MemtableFactory::HashSkipList {
+    ///         bucket_count: 1_000_000,
+    ///         height: 4,
+    ///         branching_factor: 4,
+    ///     });
+    ///     let db = DB::open(&opts, path).unwrap();
+    ///     for key in &[b"aaa1", b"aaa2", b"bbb1", b"ccc1"] {
+    ///         db.put(key, b"value").unwrap();
+    ///     }
+    ///
+    ///     let mut readopts = ReadOptions::default();
+    ///     readopts.set_total_order_seek(true);
+    ///     let iter = db.iterator_opt(IteratorMode::Start, readopts);
+    ///     assert_eq!(iter.count(), 4);
+    /// }
+    /// let _ = DB::destroy(&Options::default(), path);
+    /// ```
     pub fn set_total_order_seek(&mut self, v: bool) {
         unsafe { ffi::rocksdb_readoptions_set_total_order_seek(self.inner, v as c_uchar) }
     }
@@ -102,17 +102,20 @@ pub use crate::{
         ColumnFamilyRef, DEFAULT_COLUMN_FAMILY_NAME,
     },
     compaction_filter::Decision as CompactionDecision,
-    db::{DBWithThreadMode, LiveFile, MultiThreaded, SingleThreaded, ThreadMode, DB},
+    db::{
+        DBWithThreadMode, FileCopyGuard, LiveFile, LiveStats, MultiThreaded, SingleThreaded,
+        ThreadMode, DB,
+    },
     db_iterator::{
         DBIterator, DBIteratorWithThreadMode, DBRawIterator, DBRawIteratorWithThreadMode,
         DBWALIterator, Direction, IteratorMode,
     },
     db_options::{
-        BlockBasedIndexType, BlockBasedOptions, BottommostLevelCompaction, Cache, CompactOptions,
-        CuckooTableOptions, DBCompactionStyle, DBCompressionType, DBPath, DBRecoveryMode,
-        DataBlockIndexType, Env, FifoCompactOptions, FlushOptions, IngestExternalFileOptions,
-        MemtableFactory, Options, PlainTableFactoryOptions, ReadOptions, UniversalCompactOptions,
-        UniversalCompactionStopStyle, WriteOptions,
+        AccessHint, BlockBasedIndexType, BlockBasedOptions, BottommostLevelCompaction, Cache,
+        CompactOptions, CuckooTableOptions, DBCompactionStyle, DBCompressionType, DBPath,
+        DBRecoveryMode, DataBlockIndexType, Env, FifoCompactOptions, FlushOptions,
+        IngestExternalFileOptions, MemtableFactory, Options, PlainTableFactoryOptions, ReadOptions,
+        UniversalCompactOptions, UniversalCompactionStopStyle, WriteOptions,
     },
     db_pinnable_slice::DBPinnableSlice,
     merge_operator::MergeOperands,
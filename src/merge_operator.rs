@@ -58,6 +58,7 @@
 use libc::{self, c_char, c_int, c_void, size_t};
 use std::ffi::CString;
 use std::mem;
+use std::panic::{self, AssertUnwindSafe};
 use std::ptr;
 use std::slice;
 
@@ -123,7 +124,13 @@ pub unsafe extern "C" fn full_merge_callback<F: MergeFn, PF: MergeFn>(
             existing_value_len as usize,
         ))
     };
-    (cb.full_merge_fn)(key, oldval, operands).map_or_else(
+    // A panic must not unwind across the FFI boundary, report it as a failed merge instead.
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        (cb.full_merge_fn)(key, oldval, operands)
+    }))
+    .ok()
+    .flatten()
+    .map_or_else(
         || {
             *new_value_length = 0;
             *success = 0_u8;
@@ -150,7 +157,13 @@ pub unsafe extern "C" fn partial_merge_callback<F: MergeFn, PF: MergeFn>(
     let cb = &mut *(raw_cb as *mut MergeOperatorCallback<F, PF>);
     let operands = &mut MergeOperands::new(operands_list, operands_list_len, num_operands);
     let key = slice::from_raw_parts(raw_key as *const u8, key_len as usize);
-    (cb.partial_merge_fn)(key, None, operands).map_or_else(
+    // A panic must not unwind across the FFI boundary, report it as a failed merge instead.
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        (cb.partial_merge_fn)(key, None, operands)
+    }))
+    .ok()
+    .flatten()
+    .map_or_else(
         || {
             *new_value_length = 0;
             *success = 0_u8;
@@ -263,6 +263,23 @@ impl MemoryUsageBuilder {
 }
 
 /// Get memory usage stats from DB instances and Cache instances
+///
+/// # Examples
+///
+/// ```
+/// use rocksdb::{perf::get_memory_usage_stats, Cache, Options, DB};
+///
+/// let path = "_path_for_rocksdb_memory_usage_stats";
+/// {
+///     let db = DB::open_default(path).unwrap();
+///     let cache = Cache::new_lru_cache(1 << 20).unwrap();
+///     db.put(b"key", b"value").unwrap();
+///
+///     let stats = get_memory_usage_stats(Some(&[&db]), Some(&[&cache])).unwrap();
+///     assert!(stats.mem_table_total > 0);
+/// }
+/// let _ = DB::destroy(&Options::default(), path);
+/// ```
 pub fn get_memory_usage_stats(
     dbs: Option<&[&DB]>,
     caches: Option<&[&Cache]>,
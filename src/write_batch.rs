@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{ffi, AsColumnFamilyRef};
+use crate::{ffi, AsColumnFamilyRef, Error};
 use libc::{c_char, c_void, size_t};
 use std::slice;
 
@@ -77,7 +77,37 @@ unsafe extern "C" fn writebatch_delete_callback(state: *mut c_void, k: *const c_
     leaked_cb.delete(key.to_vec().into_boxed_slice());
 }
 
+/// Size of the header of a serialized write batch: an 8-byte sequence number
+/// followed by a 4-byte little-endian count of the operations in the batch.
+const WRITE_BATCH_HEADER_SIZE: usize = 12;
+
 impl WriteBatch {
+    /// Construct with a reference to a byte array serialized by [`WriteBatch`].
+    ///
+    /// RocksDB only checks that `data` holds the 12-byte write batch header,
+    /// so it should come from [`data`](WriteBatch::data). Returns an error if
+    /// `data` is shorter than that header.
+    pub fn from_data(data: &[u8]) -> Result<Self, Error> {
+        if data.len() < WRITE_BATCH_HEADER_SIZE {
+            return Err(Error::new(format!(
+                "serialized write batch must be at least {} bytes, got {}",
+                WRITE_BATCH_HEADER_SIZE,
+                data.len()
+            )));
+        }
+        Ok(Self::from_data_unchecked(data))
+    }
+
+    fn from_data_unchecked(data: &[u8]) -> Self {
+        unsafe {
+            let ptr = data.as_ptr();
+            let len = data.len();
+            Self {
+                inner: ffi::rocksdb_writebatch_create_from(ptr as *const c_char, len as size_t),
+            }
+        }
+    }
+
     pub fn len(&self) -> usize {
         unsafe { ffi::rocksdb_writebatch_count(self.inner) as usize }
     }
@@ -91,10 +121,40 @@ impl WriteBatch {
         }
     }
 
+    /// Return a reference to a byte array which represents a serialized version of the batch.
+    pub fn data(&self) -> &[u8] {
+        unsafe {
+            let mut batch_size: size_t = 0;
+            let batch_data = ffi::rocksdb_writebatch_data(self.inner, &mut batch_size);
+            slice::from_raw_parts(batch_data as *const u8, batch_size as usize)
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
 
+    /// Append the operations of `other` to the end of this batch.
+    ///
+    /// Afterwards `len()` is the sum of both batches' counts, and writing this
+    /// batch applies the operations of both, in order.
+    ///
+    /// The C API has no append, so this concatenates the serialized batches and
+    /// patches the count in the header, which relies on RocksDB's internal
+    /// write batch format. The underlying batch is replaced by one rebuilt
+    /// from that data.
+    pub fn append(&mut self, other: &WriteBatch) {
+        if other.is_empty() {
+            return;
+        }
+
+        let mut data = self.data().to_vec();
+        data.extend_from_slice(&other.data()[WRITE_BATCH_HEADER_SIZE..]);
+        let count = (self.len() + other.len()) as u32;
+        data[8..WRITE_BATCH_HEADER_SIZE].copy_from_slice(&count.to_le_bytes());
+        *self = Self::from_data_unchecked(&data);
+    }
+
     /// Iterate the put and delete operations within this write batch. Note that
     /// this does _not_ return an `Iterator` but instead will invoke the `put()`
     /// and `delete()` member functions of the provided `WriteBatchIterator`
@@ -219,6 +279,59 @@ impl WriteBatch {
         }
     }
 
+    /// Removes the database entries for all the given keys.
+    pub fn delete_keys<K, I>(&mut self, keys: I)
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        for key in keys {
+            self.delete(key);
+        }
+    }
+
+    /// Same as `delete_keys` but for a specific column family.
+    pub fn delete_keys_cf<K, I>(&mut self, cf: &impl AsColumnFamilyRef, keys: I)
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        for key in keys {
+            self.delete_cf(cf, key);
+        }
+    }
+
+    /// Removes the database entry for key, assuming it was written exactly once.
+    ///
+    /// Unlike `delete`, the tombstone is dropped as soon as it meets the put it
+    /// removes during compaction. The behavior is undefined if the key was
+    /// overwritten or merged, or if it is deleted more than once.
+    pub fn single_delete<K: AsRef<[u8]>>(&mut self, key: K) {
+        let key = key.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_singledelete(
+                self.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+            );
+        }
+    }
+
+    /// Same as `single_delete` but for a specific column family.
+    pub fn single_delete_cf<K: AsRef<[u8]>>(&mut self, cf: &impl AsColumnFamilyRef, key: K) {
+        let key = key.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_singledelete_cf(
+                self.inner,
+                cf.inner(),
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+            );
+        }
+    }
+
     /// Remove database entries from start key to end key.
     ///
     /// Removes the database entries in the range ["begin_key", "end_key"), i.e.,
@@ -0,0 +1,60 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod util;
+
+use std::cmp::Ordering;
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+use rocksdb::{Options, DB};
+use util::DBPath;
+
+const CHILD_ENV: &str = "RUST_ROCKSDB_COMPARATOR_PANIC_CHILD";
+
+fn panicking_compare(a: &[u8], b: &[u8]) -> Ordering {
+    if a == b"poison" || b == b"poison" {
+        panic!("cannot compare poisoned key");
+    }
+    a.cmp(b)
+}
+
+#[test]
+fn comparator_panic_test() {
+    if let Some(path) = env::var_os(CHILD_ENV) {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_comparator("panicking comparator", panicking_compare);
+        let db = DB::open(&opts, &path).unwrap();
+
+        db.put(b"a", b"1").unwrap();
+        // inserting into the memtable compares against "a" and aborts
+        db.put(b"poison", b"2").unwrap();
+        return;
+    }
+
+    // the panic must not unwind into RocksDB, so run the DB in a child
+    // process and check that it was aborted
+    let path = DBPath::new("_rust_rocksdb_comparator_panic_test");
+    let db_path: &Path = (&path).as_ref();
+    let output = Command::new(env::current_exe().unwrap())
+        .args(&["comparator_panic_test", "--exact", "--nocapture"])
+        .env(CHILD_ENV, db_path)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot compare poisoned key"));
+}
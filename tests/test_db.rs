@@ -14,16 +14,17 @@
 
 mod util;
 
-use std::{mem, sync::Arc, thread, time::Duration};
+use std::{fs, mem, sync::Arc, thread, time::Duration};
 
 use pretty_assertions::assert_eq;
 
 use rocksdb::{
     perf::get_memory_usage_stats, BlockBasedOptions, BottommostLevelCompaction, Cache,
-    CompactOptions, CuckooTableOptions, DBCompactionStyle, DBWithThreadMode, Env, Error,
-    FifoCompactOptions, IteratorMode, MultiThreaded, Options, PerfContext, PerfMetric, ReadOptions,
-    SingleThreaded, SliceTransform, Snapshot, UniversalCompactOptions,
-    UniversalCompactionStopStyle, WriteBatch, DB,
+    ColumnFamilyDescriptor, CompactOptions, CuckooTableOptions, DBCompactionStyle,
+    DBCompressionType, DBWithThreadMode, Env, Error, FifoCompactOptions, IteratorMode,
+    MergeOperands, MultiThreaded, Options, PerfContext, PerfMetric, ReadOptions, SingleThreaded,
+    SliceTransform, Snapshot, UniversalCompactOptions, UniversalCompactionStopStyle, WriteBatch,
+    DB,
 };
 use util::DBPath;
 
@@ -134,6 +135,89 @@ fn writebatch_works() {
     }
 }
 
+fn concat_merge(
+    _key: &[u8],
+    existing_val: Option<&[u8]>,
+    operands: &mut MergeOperands,
+) -> Option<Vec<u8>> {
+    let mut result = existing_val.map(<[u8]>::to_vec).unwrap_or_default();
+    for op in operands {
+        result.extend_from_slice(op);
+    }
+    Some(result)
+}
+
+#[test]
+fn writebatch_cf_works() {
+    let path = DBPath::new("_rust_rocksdb_writebatch_cf_works");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        opts.set_merge_operator_associative("concat", concat_merge);
+        let cf_opts = opts.clone();
+        let db = DB::open_cf_descriptors(
+            &opts,
+            &path,
+            vec![ColumnFamilyDescriptor::new("cf1", cf_opts)],
+        )
+        .unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        let mut batch = WriteBatch::default();
+        batch.put_cf(&cf1, b"k1", b"a");
+        batch.merge_cf(&cf1, b"k1", b"b");
+        batch.put_cf(&cf1, b"k2", b"v2");
+        batch.put_cf(&cf1, b"k3", b"v3");
+        batch.put(b"k4", b"v4");
+        db.write(batch).unwrap();
+
+        let mut batch = WriteBatch::default();
+        batch.merge(b"k4", b"v4");
+        batch.delete_cf(&cf1, b"k2");
+        batch.single_delete_cf(&cf1, b"k3");
+        assert_eq!(batch.len(), 3);
+        db.write(batch).unwrap();
+
+        assert_eq!(db.get_cf(&cf1, b"k1").unwrap().unwrap(), b"ab");
+        assert!(db.get_cf(&cf1, b"k2").unwrap().is_none());
+        assert!(db.get_cf(&cf1, b"k3").unwrap().is_none());
+        assert_eq!(db.get(b"k4").unwrap().unwrap(), b"v4v4");
+
+        let mut batch = WriteBatch::default();
+        batch.put(b"k5", b"v5");
+        db.write(batch).unwrap();
+        let mut batch = WriteBatch::default();
+        batch.single_delete(b"k5");
+        db.write(batch).unwrap();
+        assert!(db.get(b"k5").unwrap().is_none());
+    }
+}
+
+#[test]
+fn single_delete_test() {
+    let path = DBPath::new("_rust_rocksdb_single_delete_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, &["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        db.put(b"k1", b"v1").unwrap();
+        db.put_cf(&cf1, b"k2", b"v2").unwrap();
+        db.flush().unwrap();
+
+        db.single_delete(b"k1").unwrap();
+        db.single_delete_cf(&cf1, b"k2").unwrap();
+        assert!(db.get(b"k1").unwrap().is_none());
+        assert!(db.get_cf(&cf1, b"k2").unwrap().is_none());
+
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        assert!(db.get(b"k1").unwrap().is_none());
+    }
+}
+
 #[test]
 fn iterator_test() {
     let path = DBPath::new("_rust_rocksdb_iteratortest");
@@ -300,6 +384,52 @@ fn sync_snapshot_test() {
     assert!(handler_2.join().unwrap());
 }
 
+#[test]
+fn snapshot_concurrent_reads_test() {
+    let path = DBPath::new("_rust_rocksdb_snapshot_concurrent_reads_test");
+    let db = DB::open_default(&path).unwrap();
+
+    for i in 0..100 {
+        db.put(format!("k{}", i), b"old").unwrap();
+    }
+    let wrapper = SnapshotWrapper::new(&db);
+    for i in 0..100 {
+        db.put(format!("k{}", i), b"new").unwrap();
+    }
+
+    let handlers: Vec<_> = (0..4)
+        .map(|_| {
+            let wrapper = wrapper.clone();
+            thread::spawn(move || (0..100).all(|i| wrapper.check(format!("k{}", i), b"old")))
+        })
+        .collect();
+    for handler in handlers {
+        assert!(handler.join().unwrap());
+    }
+}
+
+#[test]
+fn snapshot_get_pinned_test() {
+    let path = DBPath::new("_rust_rocksdb_snapshot_get_pinned_test");
+    {
+        let db = DB::open_default(&path).unwrap();
+        let old_value = vec![b'o'; 64 * 1024];
+        let new_value = vec![b'n'; 64 * 1024];
+
+        db.put(b"k1", &old_value).unwrap();
+        let snap = db.snapshot();
+        db.put(b"k1", &new_value).unwrap();
+
+        let pinned = snap.get_pinned(b"k1").unwrap().unwrap();
+        assert_eq!(&*pinned, old_value.as_slice());
+        assert_eq!(
+            &*db.get_pinned(b"k1").unwrap().unwrap(),
+            new_value.as_slice()
+        );
+        assert!(snap.get_pinned(b"k2").unwrap().is_none());
+    }
+}
+
 #[test]
 fn set_option_test() {
     let path = DBPath::new("_rust_rocksdb_set_optionstest");
@@ -548,6 +678,34 @@ fn test_open_cf_with_ttl() {
     assert!(db.get_cf(&cf, b"key1").unwrap().is_none());
 }
 
+#[test]
+fn test_open_cf_descriptors_with_ttl() {
+    let path = DBPath::new("_rust_rocksdb_test_open_cf_descriptors_with_ttl");
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let cfs = vec![
+        ColumnFamilyDescriptor::new_with_ttl("short", Options::default(), Duration::from_secs(1)),
+        ColumnFamilyDescriptor::new_with_ttl("long", Options::default(), Duration::from_secs(3600)),
+    ];
+    let db =
+        DB::open_cf_descriptors_with_ttl(&opts, &path, cfs, Duration::from_secs(3600)).unwrap();
+    let short = db.cf_handle("short").unwrap();
+    let long = db.cf_handle("long").unwrap();
+    db.put_cf(&short, b"key1", b"value1").unwrap();
+    db.put_cf(&long, b"key1", b"value1").unwrap();
+    assert_eq!(db.get_cf(&short, b"key1").unwrap().unwrap(), b"value1");
+    assert_eq!(db.get_cf(&long, b"key1").unwrap().unwrap(), b"value1");
+
+    thread::sleep(Duration::from_secs(2));
+    db.compact_range_cf(&short, None::<&[u8]>, None::<&[u8]>);
+    db.compact_range_cf(&long, None::<&[u8]>, None::<&[u8]>);
+
+    assert!(db.get_cf(&short, b"key1").unwrap().is_none());
+    assert_eq!(db.get_cf(&long, b"key1").unwrap().unwrap(), b"value1");
+}
+
 #[test]
 fn test_open_as_single_threaded() {
     let primary_path = DBPath::new("_rust_rocksdb_test_open_as_single_threaded");
@@ -625,6 +783,42 @@ fn compact_range_test() {
     }
 }
 
+#[test]
+fn universal_compaction_test() {
+    let path = DBPath::new("_rust_rocksdb_universal_compaction_test");
+    {
+        let mut uni_co_opts = UniversalCompactOptions::default();
+        uni_co_opts.set_size_ratio(1);
+        uni_co_opts.set_min_merge_width(2);
+        uni_co_opts.set_max_merge_width(8);
+        uni_co_opts.set_max_size_amplification_percent(100);
+        uni_co_opts.set_stop_style(UniversalCompactionStopStyle::Similar);
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_compaction_style(DBCompactionStyle::Universal);
+        opts.set_universal_compaction_options(&uni_co_opts);
+        let db = DB::open(&opts, &path).unwrap();
+
+        for batch in 0..8 {
+            let mut wb = WriteBatch::default();
+            for i in 0..1000 {
+                wb.put(format!("key{:04}", i), format!("value{}-{:04}", batch, i));
+            }
+            db.write(wb).unwrap();
+            db.flush().unwrap();
+        }
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+        for i in 0..1000 {
+            assert_eq!(
+                db.get(format!("key{:04}", i)).unwrap().unwrap(),
+                format!("value7-{:04}", i).as_bytes()
+            );
+        }
+    }
+}
+
 #[test]
 fn fifo_compaction_test() {
     let path = DBPath::new("_rust_rocksdb_fifo_compaction_test");
@@ -663,6 +857,43 @@ fn fifo_compaction_test() {
     }
 }
 
+#[test]
+fn fifo_compaction_max_size_test() {
+    let path = DBPath::new("_rust_rocksdb_fifo_compaction_max_size_test");
+    {
+        let max_size = 256 << 10; // 256KB
+        let mut fifo_co_opts = FifoCompactOptions::default();
+        fifo_co_opts.set_max_table_files_size(max_size);
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_compression_type(DBCompressionType::None);
+        opts.set_compaction_style(DBCompactionStyle::Fifo);
+        opts.set_fifo_compaction_options(&fifo_co_opts);
+        let db = DB::open(&opts, &path).unwrap();
+
+        // each flush produces a ~100KB table file
+        let value = vec![b'v'; 1024];
+        for batch in 0..10 {
+            for i in 0..100 {
+                db.put(format!("key{}-{:03}", batch, i), &value).unwrap();
+            }
+            db.flush().unwrap();
+        }
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+        let total = db
+            .property_int_value("rocksdb.total-sst-files-size")
+            .unwrap()
+            .unwrap();
+        assert!(total <= max_size);
+
+        // the oldest tables were dropped, the newest one is still there
+        assert!(db.get(b"key0-000").unwrap().is_none());
+        assert_eq!(db.get(b"key9-099").unwrap().unwrap(), value);
+    }
+}
+
 #[test]
 fn env_and_dbpaths_test() {
     let path = DBPath::new("_rust_rocksdb_dbpath_test");
@@ -1041,3 +1272,172 @@ fn cuckoo() {
         assert!(db.get(b"k1").unwrap().is_none());
     }
 }
+
+#[test]
+fn cuckoo_read_only() {
+    let path = DBPath::new("_rust_rocksdb_cuckoo_read_only");
+
+    let mut factory_opts = CuckooTableOptions::default();
+    factory_opts.set_hash_ratio(0.9);
+    factory_opts.set_max_search_depth(100);
+    factory_opts.set_cuckoo_block_size(5);
+
+    let mut opts = Options::default();
+    opts.set_cuckoo_table_factory(&factory_opts);
+    // cuckoo tables can only be read through mmap
+    opts.set_allow_mmap_reads(true);
+    opts.create_if_missing(true);
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        for i in 0..100 {
+            db.put(format!("key{:03}", i), format!("value{:03}", i))
+                .unwrap();
+        }
+        db.flush().unwrap();
+    }
+    {
+        let db = DB::open_for_read_only(&opts, &path, false).unwrap();
+        for i in 0..100 {
+            assert_eq!(
+                db.get(format!("key{:03}", i)).unwrap().unwrap(),
+                format!("value{:03}", i).as_bytes()
+            );
+        }
+        assert!(db.get(b"missing").unwrap().is_none());
+    }
+}
+
+#[test]
+fn delete_file_in_range_test() {
+    let path = DBPath::new("_rust_rocksdb_delete_file_in_range_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        // produce many small sst files in the bottommost level
+        opts.set_target_file_size_base(32 << 10); // 32KB
+        let db = DB::open(&opts, &path).unwrap();
+
+        let value = vec![b'v'; 100];
+        let mut batch = WriteBatch::default();
+        for i in 0..10_000 {
+            batch.put(format!("{:0>5}", i).as_bytes(), &value);
+        }
+        db.write(batch).unwrap();
+        db.flush().unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+        let (from, to) = (format!("{:0>5}", 2000), format!("{:0>5}", 8000));
+        let contained: Vec<_> = db
+            .live_files()
+            .unwrap()
+            .into_iter()
+            .filter(|f| {
+                f.level > 0
+                    && f.start_key.as_ref().unwrap().as_slice() >= from.as_bytes()
+                    && f.end_key.as_ref().unwrap().as_slice() <= to.as_bytes()
+            })
+            .collect();
+        assert!(!contained.is_empty());
+
+        let files_before = db.live_files().unwrap().len();
+        db.delete_file_in_range(&from, &to).unwrap();
+        assert_eq!(
+            db.live_files().unwrap().len(),
+            files_before - contained.len()
+        );
+
+        // keys in fully contained files are gone
+        for f in &contained {
+            assert!(db.get(f.start_key.as_ref().unwrap()).unwrap().is_none());
+            assert!(db.get(f.end_key.as_ref().unwrap()).unwrap().is_none());
+        }
+        // keys outside of the range are untouched
+        assert!(db.get(b"00000").unwrap().is_some());
+        assert!(db.get(b"09999").unwrap().is_some());
+    }
+}
+
+#[test]
+fn file_deletions_test() {
+    let path = DBPath::new("_rust_rocksdb_file_deletions_test");
+    {
+        let db = DB::open_default(&path).unwrap();
+
+        db.disable_file_deletions().unwrap();
+        // nested pairs are allowed
+        db.disable_file_deletions().unwrap();
+        for i in 0..3 {
+            db.put(format!("key{}", i), format!("value{}", i)).unwrap();
+            db.flush().unwrap();
+        }
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        db.enable_file_deletions(false).unwrap();
+        db.enable_file_deletions(false).unwrap();
+
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        for i in 0..3 {
+            assert_eq!(
+                db.get(format!("key{}", i)).unwrap().unwrap(),
+                format!("value{}", i).as_bytes()
+            );
+        }
+
+        db.disable_file_deletions().unwrap();
+        db.enable_file_deletions(true).unwrap();
+    }
+}
+
+#[test]
+fn prepare_for_file_copy_test() {
+    let path = DBPath::new("_rust_rocksdb_prepare_for_file_copy_test");
+    let copy_path = DBPath::new("_rust_rocksdb_prepare_for_file_copy_test_copy");
+    {
+        let db = DB::open_default(&path).unwrap();
+        for i in 0..100 {
+            db.put(format!("key{:03}", i), format!("value{:03}", i))
+                .unwrap();
+        }
+
+        let guard = db.prepare_for_file_copy().unwrap();
+        let live_files = guard.live_files().unwrap();
+        assert!(!live_files.is_empty());
+
+        fs::create_dir_all(&copy_path).unwrap();
+        for file in &live_files {
+            let name = file.name.trim_start_matches('/');
+            fs::copy(path.as_ref().join(name), copy_path.as_ref().join(name)).unwrap();
+        }
+        for entry in fs::read_dir(&path).unwrap() {
+            let name = entry.unwrap().file_name().into_string().unwrap();
+            if name == "CURRENT" || name.starts_with("MANIFEST-") || name.starts_with("OPTIONS-") {
+                fs::copy(path.as_ref().join(&name), copy_path.as_ref().join(&name)).unwrap();
+            }
+        }
+        drop(guard);
+    }
+    {
+        let db = DB::open_default(&copy_path).unwrap();
+        for i in 0..100 {
+            assert_eq!(
+                db.get(format!("key{:03}", i)).unwrap().unwrap(),
+                format!("value{:03}", i).as_bytes()
+            );
+        }
+    }
+}
+
+#[test]
+fn memory_usage_stats_test() {
+    let path = DBPath::new("_rust_rocksdb_memory_usage_stats_test");
+    {
+        let db = DB::open_default(&path).unwrap();
+        for i in 0..1000 {
+            db.put(format!("key{}", i), format!("value{}", i)).unwrap();
+        }
+
+        let stats = get_memory_usage_stats(Some(&[&db]), None).unwrap();
+        assert!(stats.mem_table_total > 0);
+        assert!(stats.mem_table_unflushed > 0);
+        assert!(stats.mem_table_unflushed <= stats.mem_table_total);
+    }
+}
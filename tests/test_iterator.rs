@@ -14,9 +14,11 @@
 
 mod util;
 
+use std::cmp::Ordering;
+
 use pretty_assertions::assert_eq;
 
-use rocksdb::{Direction, IteratorMode, MemtableFactory, Options, DB};
+use rocksdb::{Direction, IteratorMode, MemtableFactory, Options, ReadOptions, DB};
 use util::DBPath;
 
 fn cba(input: &[u8]) -> Box<[u8]> {
@@ -300,6 +302,103 @@ fn test_full_iterator() {
     }
 }
 
+#[test]
+fn test_iterator_opt_total_order_seek() {
+    let path = DBPath::new("_rust_rocksdb_iterator_opt_total_order_seek_test");
+    {
+        let prefix_extractor = rocksdb::SliceTransform::create_fixed_prefix(3);
+        let factory = MemtableFactory::HashSkipList {
+            bucket_count: 1_000_000,
+            height: 4,
+            branching_factor: 4,
+        };
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_prefix_extractor(prefix_extractor);
+        opts.set_allow_concurrent_memtable_write(false);
+        opts.set_memtable_factory(factory);
+
+        let db = DB::open(&opts, &path).unwrap();
+        for k in &[b"aaa1", b"aaa2", b"bbb1", b"ccc1"] {
+            db.put(k, k).unwrap();
+        }
+
+        // the hash memtable only orders keys within a prefix, so a full scan
+        // needs total order seek
+        let mut readopts = ReadOptions::default();
+        readopts.set_total_order_seek(true);
+        let keys: Vec<_> = db
+            .iterator_opt(IteratorMode::Start, readopts)
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(
+            keys,
+            vec![cba(b"aaa1"), cba(b"aaa2"), cba(b"bbb1"), cba(b"ccc1")]
+        );
+
+        // a prefix seek stops at the end of the seek key's prefix, while total
+        // order seek ignores prefix_same_as_start and continues past it
+        let seek_from_bbb = |total_order_seek: bool| -> Vec<_> {
+            let mut readopts = ReadOptions::default();
+            readopts.set_prefix_same_as_start(true);
+            readopts.set_total_order_seek(total_order_seek);
+            db.iterator_opt(IteratorMode::From(b"bbb", Direction::Forward), readopts)
+                .map(|(k, _)| k)
+                .collect()
+        };
+        assert_eq!(seek_from_bbb(false), vec![cba(b"bbb1")]);
+        assert_eq!(seek_from_bbb(true), vec![cba(b"bbb1"), cba(b"ccc1")]);
+    }
+}
+
+#[test]
+fn test_iterator_seek_for_prev() {
+    let path = DBPath::new("_rust_rocksdb_iterator_seek_for_prev_test");
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+        db.put(b"k4", b"v4").unwrap();
+
+        // Starts from the previous key when the key doesn't exist
+        let iter = db.iterator(IteratorMode::From(b"k3", Direction::Reverse));
+        assert_eq!(
+            iter.collect::<Vec<_>>(),
+            vec![(cba(b"k2"), cba(b"v2")), (cba(b"k1"), cba(b"v1"))]
+        );
+
+        // Starts from the key itself when it exists
+        let iter = db.iterator(IteratorMode::From(b"k4", Direction::Reverse));
+        assert_eq!(
+            iter.map(|(k, _)| k).collect::<Vec<_>>(),
+            vec![cba(b"k4"), cba(b"k2"), cba(b"k1")]
+        );
+    }
+}
+
+#[test]
+fn test_iterator_with_readahead() {
+    let path = DBPath::new("_rust_rocksdb_iterator_with_readahead_test");
+    {
+        let db = DB::open_default(&path).unwrap();
+        for i in 0..1000 {
+            db.put(format!("key{:04}", i), format!("value{:04}", i))
+                .unwrap();
+        }
+        db.flush().unwrap();
+
+        let mut readopts = ReadOptions::default();
+        readopts.set_readahead_size(2 * 1024 * 1024);
+        let iter = db.iterator_opt(IteratorMode::Start, readopts);
+        let keys: Vec<_> = iter.map(|(k, _)| k).collect();
+        let expected: Vec<_> = (0..1000)
+            .map(|i| format!("key{:04}", i).into_bytes().into_boxed_slice())
+            .collect();
+        assert_eq!(keys, expected);
+    }
+}
+
 fn custom_iter(db: &'_ DB) -> impl Iterator<Item = usize> + '_ {
     db.iterator(IteratorMode::Start)
         .map(|(_, db_value)| db_value.len())
@@ -321,3 +420,226 @@ fn test_iterator_outlive_db() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/fail/iterator_outlive_db.rs");
 }
+
+#[test]
+fn test_iterator_max_sequential_skip() {
+    let path = DBPath::new("_rust_rocksdb_iterator_max_sequential_skip_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_max_sequential_skip_in_iterations(1000);
+        let db = DB::open(&opts, &path).unwrap();
+
+        db.put(b"a", b"1").unwrap();
+        for i in 0..500 {
+            db.put(b"b", format!("{}", i)).unwrap();
+        }
+        db.delete(b"b").unwrap();
+        db.put(b"c", b"3").unwrap();
+
+        let iter = db.iterator(IteratorMode::Start);
+        let expected = vec![(cba(b"a"), cba(b"1")), (cba(b"c"), cba(b"3"))];
+        assert_eq!(iter.collect::<Vec<_>>(), expected);
+
+        let iter = db.iterator(IteratorMode::End);
+        let expected = vec![(cba(b"c"), cba(b"3")), (cba(b"a"), cba(b"1"))];
+        assert_eq!(iter.collect::<Vec<_>>(), expected);
+    }
+}
+
+#[test]
+fn test_iterator_mode_prefix() {
+    let path = DBPath::new("_rust_rocksdb_iterator_mode_prefix_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, &["cf1"]).unwrap();
+        let cf = db.cf_handle("cf1").unwrap();
+
+        for key in &[
+            "item:1", "user:1", "user:2", "user:3", "userx", "users:1", "zzz",
+        ] {
+            db.put_cf(&cf, key, b"value").unwrap();
+        }
+
+        let keys: Vec<_> = db
+            .iterator_cf(&cf, IteratorMode::Prefix(b"user:"))
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(keys, vec![cba(b"user:1"), cba(b"user:2"), cba(b"user:3")]);
+
+        let mut iter = db.iterator_cf(&cf, IteratorMode::Prefix(b"item:"));
+        assert_eq!(iter.next(), Some((cba(b"item:1"), cba(b"value"))));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+
+        // switching modes drops the prefix bound
+        iter.set_mode(IteratorMode::Start);
+        assert_eq!(iter.count(), 7);
+
+        assert_eq!(
+            db.iterator_cf(&cf, IteratorMode::Prefix(b"none:")).count(),
+            0
+        );
+    }
+}
+
+fn length_first_compare(a: &[u8], b: &[u8]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+#[test]
+fn test_iterator_mode_prefix_stays_exhausted() {
+    let path = DBPath::new("_rust_rocksdb_iterator_mode_prefix_exhausted_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_comparator("length first", length_first_compare);
+        let db = DB::open(&opts, &path).unwrap();
+
+        // ordered as a1, b1, a22: keys with the prefix are not contiguous
+        for key in &["a1", "b1", "a22"] {
+            db.put(key, b"value").unwrap();
+        }
+
+        let mut iter = db.iterator(IteratorMode::Prefix(b"a"));
+        assert!(iter.valid());
+        assert_eq!(iter.next(), Some((cba(b"a1"), cba(b"value"))));
+        assert_eq!(iter.next(), None);
+        assert!(!iter.valid());
+        assert_eq!(iter.next(), None);
+        assert!(!iter.valid());
+
+        // set_mode resets the exhausted state
+        iter.set_mode(IteratorMode::Prefix(b"a"));
+        assert!(iter.valid());
+        assert_eq!(iter.next(), Some((cba(b"a1"), cba(b"value"))));
+        assert_eq!(iter.next(), None);
+    }
+}
+
+#[test]
+fn test_iterator_for_each_pinned() {
+    let path = DBPath::new("_rust_rocksdb_iterator_for_each_pinned_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, &["cf1"]).unwrap();
+        let cf = db.cf_handle("cf1").unwrap();
+
+        let mut expected = 0;
+        for i in 0..100 {
+            let value = vec![b'v'; i];
+            db.put_cf(&cf, format!("key{:03}", i), &value).unwrap();
+            expected += value.len();
+        }
+
+        let mut total = 0;
+        let mut count = 0;
+        db.iterator_cf(&cf, IteratorMode::Start)
+            .for_each_pinned(|_key, value| {
+                total += value.len();
+                count += 1;
+            });
+        assert_eq!(total, expected);
+        assert_eq!(count, 100);
+
+        let mut keys = Vec::new();
+        db.iterator_cf(&cf, IteratorMode::From(b"key002", Direction::Reverse))
+            .for_each_pinned(|key, _value| keys.push(key.to_vec()));
+        assert_eq!(
+            keys,
+            vec![b"key002".to_vec(), b"key001".to_vec(), b"key000".to_vec()]
+        );
+    }
+}
+
+#[test]
+fn test_prefix_extractor_outlives_options() {
+    let path = DBPath::new("_rust_rocksdb_prefix_extractor_outlives_options_test");
+    {
+        let db = {
+            let mut opts = Options::default();
+            opts.create_if_missing(true);
+            opts.set_prefix_extractor(rocksdb::SliceTransform::create_fixed_prefix(3));
+            DB::open(&opts, &path).unwrap()
+        };
+
+        for key in &[b"aaa1", b"aaa2", b"bbb1", b"bbb2"] {
+            db.put(key, key).unwrap();
+        }
+        db.flush().unwrap();
+
+        let keys: Vec<_> = db.prefix_iterator(b"bbb").map(|(k, _)| k).collect();
+        assert_eq!(keys, vec![cba(b"bbb1"), cba(b"bbb2")]);
+    }
+}
+
+#[test]
+fn test_range_iterator() {
+    let path = DBPath::new("_rust_rocksdb_range_iterator_test");
+    {
+        let db = DB::open_default(&path).unwrap();
+        for key in &[b"a", b"b", b"c", b"d", b"e"] {
+            db.put(key, key).unwrap();
+        }
+
+        let keys: Vec<_> = db
+            .range_iterator(b"b", b"e", Direction::Forward)
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(keys, vec![cba(b"b"), cba(b"c"), cba(b"d")]);
+
+        // reverse scans yield (start, end]
+        let keys: Vec<_> = db
+            .range_iterator(b"b", b"e", Direction::Reverse)
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(keys, vec![cba(b"e"), cba(b"d"), cba(b"c")]);
+
+        // bounds need not be existing keys
+        let keys: Vec<_> = db
+            .range_iterator(&b"bb"[..], &b"dd"[..], Direction::Reverse)
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(keys, vec![cba(b"d"), cba(b"c")]);
+        let keys: Vec<_> = db
+            .range_iterator(&b"a"[..], &b"bb"[..], Direction::Reverse)
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(keys, vec![cba(b"b")]);
+
+        assert_eq!(db.range_iterator(b"c", b"c", Direction::Forward).count(), 0);
+        assert_eq!(db.range_iterator(b"c", b"c", Direction::Reverse).count(), 0);
+    }
+}
+
+#[test]
+fn test_count_remaining_and_count_range() {
+    let path = DBPath::new("_rust_rocksdb_count_remaining_test");
+    {
+        let db = DB::open_default(&path).unwrap();
+        for i in 0..100 {
+            db.put(format!("key{:03}", i), b"value").unwrap();
+        }
+
+        assert_eq!(db.iterator(IteratorMode::Start).count_remaining(), 100);
+        assert_eq!(db.iterator(IteratorMode::End).count_remaining(), 100);
+        assert_eq!(
+            db.iterator(IteratorMode::From(b"key050", Direction::Reverse))
+                .count_remaining(),
+            51
+        );
+
+        let mut iter = db.iterator(IteratorMode::Start);
+        iter.next();
+        iter.next();
+        assert_eq!(iter.count_remaining(), 98);
+
+        assert_eq!(db.count_range(b"key010", b"key020"), 10);
+        assert_eq!(db.count_range(&b"key"[..], &b"kez"[..]), 100);
+        assert_eq!(db.count_range(b"key020", b"key010"), 0);
+    }
+}
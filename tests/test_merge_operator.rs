@@ -341,3 +341,67 @@ fn test_merge_state() {
     }
     assert!(DB::destroy(&opts, path).is_ok());
 }
+
+fn test_panicking_merge(
+    _new_key: &[u8],
+    _existing_val: Option<&[u8]>,
+    _operands: &mut MergeOperands,
+) -> Option<Vec<u8>> {
+    panic!("malformed merge operand");
+}
+
+#[test]
+fn merge_panic_test() {
+    let db_path = DBPath::new("_rust_rocksdb_merge_panic_test");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_merge_operator_associative("panicking operator", test_panicking_merge);
+
+    let db = DB::open(&opts, &db_path).unwrap();
+    db.put(b"k1", b"a").unwrap();
+    db.merge(b"k1", b"b").unwrap();
+
+    // the panic is caught and surfaces as a failed merge
+    assert!(db.get(b"k1").is_err());
+
+    // the db is still usable afterwards
+    db.put(b"k2", b"c").unwrap();
+    assert_eq!(db.get(b"k2").unwrap().unwrap(), b"c");
+}
+
+fn test_counter_merge(
+    _new_key: &[u8],
+    existing_val: Option<&[u8]>,
+    operands: &mut MergeOperands,
+) -> Option<Vec<u8>> {
+    let sum = existing_val
+        .into_iter()
+        .chain(operands)
+        .map(|slice| {
+            let mut bytes: [u8; 8] = Default::default();
+            bytes.copy_from_slice(slice);
+            u64::from_le_bytes(bytes)
+        })
+        .sum::<u64>();
+    Some(sum.to_le_bytes().to_vec())
+}
+
+#[test]
+fn max_successive_merges_test() {
+    let db_path = DBPath::new("_rust_rocksdb_max_successive_merges_test");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_max_successive_merges(4);
+    opts.set_merge_operator_associative("counter", test_counter_merge);
+
+    let db = DB::open(&opts, &db_path).unwrap();
+    db.put(b"counter", 0u64.to_le_bytes()).unwrap();
+    for _ in 0..100 {
+        db.merge(b"counter", 1u64.to_le_bytes()).unwrap();
+    }
+
+    let value = db.get(b"counter").unwrap().unwrap();
+    let mut bytes: [u8; 8] = Default::default();
+    bytes.copy_from_slice(&value);
+    assert_eq!(u64::from_le_bytes(bytes), 100);
+}
@@ -39,3 +39,24 @@ fn test_pinnable_slice() {
 
     assert_eq!(b"12345", &pinnable_slice[5..10]);
 }
+
+#[test]
+fn test_pinnable_slice_matches_get() {
+    let path = DBPath::new("_rust_rocksdb_pinnable_slice_matches_get_test");
+    let db = DB::open_default(&path).unwrap();
+
+    for i in 0..10 {
+        db.put(format!("k{}", i), vec![b'v'; i * 10]).unwrap();
+    }
+
+    for i in 0..10 {
+        let key = format!("k{}", i);
+        let pinned = db.get_pinned(&key).unwrap().unwrap();
+        let owned = db.get(&key).unwrap().unwrap();
+        assert_eq!(&*pinned, owned.as_slice());
+        // ownership is only taken when explicitly asked for
+        assert_eq!(pinned.to_vec(), owned);
+    }
+    assert!(db.get_pinned(b"missing").unwrap().is_none());
+    assert!(db.get(b"missing").unwrap().is_none());
+}
@@ -72,3 +72,96 @@ fn property_int_cf_test() {
         assert_eq!(total_keys, Some(0));
     }
 }
+
+#[test]
+fn aggregated_int_property_test() {
+    let n = DBPath::new("_rust_rocksdb_aggregated_int_property_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &n, &["cf1", "cf2"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        let cf2 = db.cf_handle("cf2").unwrap();
+        for i in 0..100 {
+            db.put_cf(&cf1, format!("key{}", i), b"value").unwrap();
+            db.put_cf(&cf2, format!("key{}", i), b"value").unwrap();
+        }
+
+        let property = "rocksdb.size-all-mem-tables";
+        let cf1_size = db.property_int_value_cf(&cf1, property).unwrap().unwrap();
+        let cf2_size = db.property_int_value_cf(&cf2, property).unwrap().unwrap();
+        let total = db.aggregated_int_property(property).unwrap().unwrap();
+
+        assert!(total > cf1_size);
+        assert!(total > cf2_size);
+        assert!(total >= cf1_size + cf2_size);
+    }
+}
+
+#[test]
+fn num_files_at_level_test() {
+    let n = DBPath::new("_rust_rocksdb_num_files_at_level_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_disable_auto_compactions(true);
+        let mut db = DB::open(&opts, &n).unwrap();
+        db.create_cf("cf1", &opts).unwrap();
+
+        for i in 0..4 {
+            db.put(format!("key{}", i), b"value").unwrap();
+            db.flush().unwrap();
+        }
+        assert_eq!(db.num_files_at_level(0).unwrap(), 4);
+
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        let num_levels = 7;
+        let total: u64 = (0..num_levels)
+            .map(|level| db.num_files_at_level(level).unwrap())
+            .sum();
+        let live_files = db.live_files().unwrap();
+        assert_eq!(total, live_files.len() as u64);
+
+        let cf = db.cf_handle("cf1").unwrap();
+        assert_eq!(db.num_files_at_level_cf(&cf, 0).unwrap(), 0);
+        assert!(db.num_files_at_level(num_levels).is_err());
+    }
+}
+
+#[test]
+fn live_stats_test() {
+    let n = DBPath::new("_rust_rocksdb_live_stats_test");
+    {
+        let db = DB::open_default(&n).unwrap();
+        for i in 0..100 {
+            db.put(format!("key{:03}", i), b"value").unwrap();
+        }
+        db.flush().unwrap();
+
+        let stats = db.live_stats().unwrap();
+        assert!(stats.estimate_num_keys.unwrap() > 0);
+        assert!(stats.total_sst_files_size.unwrap() > 0);
+        assert_eq!(stats.num_immutable_mem_table, Some(0));
+        assert!(stats.cur_size_all_mem_tables.is_some());
+    }
+}
+
+#[test]
+fn property_value_into_test() {
+    let n = DBPath::new("_rust_rocksdb_property_value_into_test");
+    {
+        let db = DB::open_default(&n).unwrap();
+        let mut buf = String::new();
+        for i in 0..10 {
+            db.put(format!("key{}", i), b"value").unwrap();
+            assert!(db.property_value_into("rocksdb.stats", &mut buf).unwrap());
+            assert!(buf.contains("Stats"));
+        }
+
+        assert!(!db
+            .property_value_into("rocksdb.does-not-exist", &mut buf)
+            .unwrap());
+        assert!(buf.is_empty());
+    }
+}
@@ -16,7 +16,7 @@ mod util;
 
 use pretty_assertions::assert_eq;
 
-use rocksdb::DB;
+use rocksdb::{DBRawIterator, Options, ReadOptions, SliceTransform, DB};
 use util::DBPath;
 
 #[test]
@@ -152,3 +152,68 @@ pub fn test_seek_for_prev() {
         assert_eq!(iter.value(), Some(b"v2".as_ref()));
     }
 }
+
+#[test]
+pub fn test_prefix_raw_iterator() {
+    let n = DBPath::new("prefix_raw_iterator");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_prefix_extractor(SliceTransform::create_fixed_prefix(3));
+        let db = DB::open(&opts, &n).unwrap();
+        db.put(b"aaa1", b"v1").unwrap();
+        db.put(b"bbb1", b"v2").unwrap();
+        db.put(b"bbb2", b"v3").unwrap();
+        db.put(b"ccc1", b"v4").unwrap();
+
+        let mut iter = db.prefix_raw_iterator(b"bbb");
+        assert_eq!(iter.key(), Some(b"bbb1".as_ref()));
+        iter.next();
+        assert_eq!(iter.key(), Some(b"bbb2".as_ref()));
+        iter.next();
+        assert_eq!(iter.valid(), false);
+
+        iter.seek_for_prev(b"bbb9");
+        assert_eq!(iter.key(), Some(b"bbb2".as_ref()));
+        iter.prev();
+        assert_eq!(iter.key(), Some(b"bbb1".as_ref()));
+        iter.prev();
+        assert_eq!(iter.valid(), false);
+    }
+}
+
+fn bounded_raw_iterator<'a>(db: &'a DB, lower: &[u8], upper: &[u8]) -> DBRawIterator<'a> {
+    // the read options and their bound buffers are moved into the iterator
+    let mut readopts = ReadOptions::default();
+    readopts.set_iterate_lower_bound(lower);
+    readopts.set_iterate_upper_bound(upper);
+    db.raw_iterator_opt(readopts)
+}
+
+#[test]
+fn test_raw_iterator_owns_bounds() {
+    let path = DBPath::new("_rust_rocksdb_test_raw_iterator_owns_bounds");
+    {
+        let db = DB::open_default(&path).unwrap();
+        for key in &[b"k1", b"k2", b"k3", b"k4", b"k5"] {
+            db.put(key, key).unwrap();
+        }
+
+        let mut iter = bounded_raw_iterator(&db, b"k2", b"k4");
+        let mut keys = Vec::new();
+        iter.seek_to_first();
+        while iter.valid() {
+            keys.push(iter.key().unwrap().to_vec());
+            iter.next();
+        }
+        assert_eq!(keys, vec![b"k2".to_vec(), b"k3".to_vec()]);
+
+        keys.clear();
+        iter.seek_to_last();
+        while iter.valid() {
+            keys.push(iter.key().unwrap().to_vec());
+            iter.prev();
+        }
+        assert_eq!(keys, vec![b"k3".to_vec(), b"k2".to_vec()]);
+    }
+}
@@ -14,11 +14,44 @@
 
 mod util;
 
-use std::{fs, io::Read as _};
+use std::{fs, io::Read as _, ops::Range, sync::Arc, thread};
 
-use rocksdb::{BlockBasedOptions, DataBlockIndexType, Options, ReadOptions, DB};
+use rocksdb::{
+    AccessHint, BlockBasedOptions, Cache, DBCompressionType, DBRecoveryMode, DataBlockIndexType,
+    MemtableFactory, Options, ReadOptions, WriteBatch, DB,
+};
 use util::DBPath;
 
+/// Writes `key{:03}` -> `value{:03}` for every index in `keys`.
+fn put_keys(db: &DB, keys: Range<usize>) {
+    for i in keys {
+        db.put(format!("key{:03}", i), format!("value{:03}", i))
+            .unwrap();
+    }
+}
+
+/// Checks that every key written by `put_keys` for `keys` reads back.
+fn assert_keys(db: &DB, keys: Range<usize>) {
+    for i in keys {
+        assert_eq!(
+            db.get(format!("key{:03}", i)).unwrap().unwrap(),
+            format!("value{:03}", i).as_bytes()
+        );
+    }
+}
+
+/// Returns the contents of the LOG file, where RocksDB dumps the options it
+/// opened the DB with.
+fn read_log(path: &DBPath) -> String {
+    let mut rocksdb_log = fs::File::open(format!("{}/LOG", path.as_ref().to_str().unwrap()))
+        .expect("rocksdb creates a LOG file");
+    let mut settings = String::new();
+    rocksdb_log
+        .read_to_string(&mut settings)
+        .expect("can read the LOG file");
+    settings
+}
+
 #[test]
 fn test_set_num_levels() {
     let n = DBPath::new("_rust_rocksdb_test_set_num_levels");
@@ -146,3 +179,681 @@ fn set_compression_options_zstd_max_train_bytes() {
         let _db = DB::open(&opts, &path).unwrap();
     }
 }
+
+#[test]
+fn test_set_pending_compaction_bytes_limits() {
+    let path = DBPath::new("_rust_rocksdb_test_set_pending_compaction_bytes_limits");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_soft_pending_compaction_bytes_limit(64 * 1024 * 1024);
+        opts.set_hard_pending_compaction_bytes_limit(1024 * 1024 * 1024);
+        let db = DB::open(&opts, &path).unwrap();
+
+        let mut batch = WriteBatch::default();
+        for i in 0..10_000 {
+            batch.put(format!("key{:05}", i), format!("value{:05}", i));
+        }
+        db.write(batch).unwrap();
+        assert_eq!(db.get(b"key09999").unwrap().unwrap(), b"value09999");
+    }
+}
+
+#[test]
+fn test_set_periodic_compaction_seconds_and_ttl() {
+    let path = DBPath::new("_rust_rocksdb_test_set_periodic_compaction_seconds_and_ttl");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_periodic_compaction_seconds(1);
+        opts.set_ttl(1);
+        let db = DB::open(&opts, &path).unwrap();
+
+        let settings = read_log(&path);
+        assert!(settings.contains("Options.periodic_compaction_seconds: 1"));
+        assert!(settings.contains("Options.ttl: 1"));
+
+        put_keys(&db, 0..100);
+        db.flush().unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        assert_keys(&db, 0..100);
+    }
+}
+
+#[test]
+fn test_set_max_write_buffer_number_to_maintain() {
+    let path = DBPath::new("_rust_rocksdb_test_set_max_write_buffer_number_to_maintain");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_max_write_buffer_number_to_maintain(2);
+        let db = DB::open(&opts, &path).unwrap();
+
+        db.put(b"k1", b"v1").unwrap();
+        let snapshot = db.snapshot();
+        db.put(b"k1", b"v2").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+        db.flush().unwrap();
+
+        assert_eq!(snapshot.get(b"k1").unwrap().unwrap(), b"v1");
+        assert!(snapshot.get(b"k2").unwrap().is_none());
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v2");
+    }
+}
+
+#[test]
+fn test_set_allow_mmap_reads() {
+    let path = DBPath::new("_rust_rocksdb_test_set_allow_mmap_reads");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_allow_mmap_reads(true);
+        let db = DB::open(&opts, &path).unwrap();
+        assert!(read_log(&path).contains("Options.allow_mmap_reads: 1"));
+
+        put_keys(&db, 0..100);
+        db.flush().unwrap();
+        assert_keys(&db, 0..100);
+    }
+}
+
+#[test]
+fn test_set_log_file_retention() {
+    let path = DBPath::new("_rust_rocksdb_test_set_log_file_retention");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_keep_log_file_num(3);
+        opts.set_max_log_file_size(64 * 1024);
+        opts.set_log_file_time_to_roll(0);
+        opts.set_recycle_log_file_num(0);
+        let _db = DB::open(&opts, &path).unwrap();
+
+        let mut rocksdb_log = fs::File::open(format!("{}/LOG", (&path).as_ref().to_str().unwrap()))
+            .expect("rocksdb creates a LOG file");
+        let mut settings = String::new();
+        rocksdb_log.read_to_string(&mut settings).unwrap();
+        assert!(settings.contains("Options.keep_log_file_num: 3"));
+        assert!(settings.contains("Options.max_log_file_size: 65536"));
+    }
+}
+
+#[test]
+fn test_set_max_background_jobs() {
+    let path = DBPath::new("_rust_rocksdb_test_set_max_background_jobs");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_max_background_jobs(6);
+        let db = DB::open(&opts, &path).unwrap();
+
+        let mut batch = WriteBatch::default();
+        for i in 0..10_000 {
+            batch.put(format!("key{:05}", i), format!("value{:05}", i));
+        }
+        db.write(batch).unwrap();
+        db.flush().unwrap();
+        assert_eq!(db.get(b"key00000").unwrap().unwrap(), b"value00000");
+    }
+}
+
+#[test]
+fn test_set_db_write_buffer_size() {
+    let path = DBPath::new("_rust_rocksdb_test_set_db_write_buffer_size");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_db_write_buffer_size(64 * 1024 * 1024);
+        opts.set_write_buffer_size(1024 * 1024);
+        let db = DB::open(&opts, &path).unwrap();
+
+        let value = vec![b'x'; 1024];
+        for i in 0..4096 {
+            db.put(format!("key{:05}", i), &value).unwrap();
+        }
+
+        for i in 0..4096 {
+            assert_eq!(db.get(format!("key{:05}", i)).unwrap().unwrap(), value);
+        }
+    }
+}
+
+#[test]
+fn test_set_unordered_write() {
+    let path = DBPath::new("_rust_rocksdb_test_set_unordered_write");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_unordered_write(true);
+        let db = Arc::new(DB::open(&opts, &path).unwrap());
+
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let db = db.clone();
+                thread::spawn(move || {
+                    for i in 0..1000 {
+                        db.put(format!("key{}-{:04}", t, i), b"value").unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        for t in 0..4 {
+            for i in 0..1000 {
+                assert!(db.get(format!("key{}-{:04}", t, i)).unwrap().is_some());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_set_optimize_filters_for_hits_and_report_bg_io_stats() {
+    let path = DBPath::new("_rust_rocksdb_test_optimize_filters_for_hits");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_optimize_filters_for_hits(true);
+        opts.set_report_bg_io_stats(true);
+        let db = DB::open(&opts, &path).unwrap();
+
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+        assert!(db.get(b"k2").unwrap().is_none());
+    }
+}
+
+#[test]
+fn test_set_skip_stats_update_on_db_open() {
+    let path = DBPath::new("_rust_rocksdb_test_set_skip_stats_update_on_db_open");
+    {
+        let db = DB::open_default(&path).unwrap();
+        put_keys(&db, 0..100);
+        db.flush().unwrap();
+    }
+    {
+        let mut opts = Options::default();
+        opts.set_skip_stats_update_on_db_open(true);
+        let db = DB::open(&opts, &path).unwrap();
+        assert!(read_log(&path).contains("Options.skip_stats_update_on_db_open: 1"));
+        assert_keys(&db, 0..100);
+    }
+}
+
+#[test]
+fn test_set_blob_files() {
+    let path = DBPath::new("_rust_rocksdb_test_set_blob_files");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_enable_blob_files(true);
+    opts.set_min_blob_size(1024);
+    opts.set_blob_file_size(1024 * 1024);
+    opts.set_blob_compression_type(DBCompressionType::None);
+
+    let value = vec![b'b'; 16 * 1024];
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        for i in 0..32 {
+            db.put(format!("key{:02}", i), &value).unwrap();
+        }
+        db.flush().unwrap();
+    }
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        for i in 0..32 {
+            assert_eq!(db.get(format!("key{:02}", i)).unwrap().unwrap(), value);
+        }
+    }
+}
+
+#[test]
+fn test_set_blob_gc() {
+    let path = DBPath::new("_rust_rocksdb_test_set_blob_gc");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_enable_blob_files(true);
+        opts.set_min_blob_size(1024);
+        opts.set_enable_blob_gc(true);
+        opts.set_blob_gc_age_cutoff(0.5);
+        let db = DB::open(&opts, &path).unwrap();
+
+        for round in 0..3u8 {
+            let value = vec![b'a' + round; 8 * 1024];
+            for i in 0..32 {
+                db.put(format!("key{:02}", i), &value).unwrap();
+            }
+            db.flush().unwrap();
+        }
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+        let latest = vec![b'c'; 8 * 1024];
+        for i in 0..32 {
+            assert_eq!(db.get(format!("key{:02}", i)).unwrap().unwrap(), latest);
+        }
+    }
+}
+
+#[test]
+fn test_set_bloom_filter() {
+    let path = DBPath::new("_rust_rocksdb_test_set_bloom_filter");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+
+        let mut block_opts = BlockBasedOptions::default();
+        block_opts.set_bloom_filter(10, false);
+        opts.set_block_based_table_factory(&block_opts);
+        let db = DB::open(&opts, &path).unwrap();
+
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+        assert!(db.get(b"k2").unwrap().is_none());
+
+        let mut rocksdb_log = fs::File::open(format!("{}/LOG", (&path).as_ref().to_str().unwrap()))
+            .expect("rocksdb creates a LOG file");
+        let mut settings = String::new();
+        rocksdb_log.read_to_string(&mut settings).unwrap();
+        assert!(settings.contains("filter_policy: rocksdb.BuiltinBloomFilter"));
+    }
+}
+
+#[test]
+fn test_set_writable_file_max_buffer_size_and_arena_block_size() {
+    let path = DBPath::new("_rust_rocksdb_test_set_writable_file_max_buffer_size");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_writable_file_max_buffer_size(512 << 10);
+        opts.set_arena_block_size(64 << 10);
+        let db = DB::open(&opts, &path).unwrap();
+
+        let settings = read_log(&path);
+        assert!(settings.contains("Options.writable_file_max_buffer_size: 524288"));
+        assert!(settings.contains("Options.arena_block_size: 65536"));
+
+        let mut batch = WriteBatch::default();
+        for i in 0..10_000 {
+            batch.put(format!("key{:05}", i), format!("value{:05}", i));
+        }
+        db.write(batch).unwrap();
+        db.flush().unwrap();
+        assert_eq!(db.get(b"key05000").unwrap().unwrap(), b"value05000");
+    }
+}
+
+#[test]
+fn test_set_inplace_update_support() {
+    let path = DBPath::new("_rust_rocksdb_test_set_inplace_update_support");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        // in-place updates are not compatible with concurrent memtable writes
+        opts.set_allow_concurrent_memtable_write(false);
+        opts.set_inplace_update_support(true);
+        let db = DB::open(&opts, &path).unwrap();
+
+        db.put(b"k1", b"value-1").unwrap();
+        db.put(b"k1", b"value-2").unwrap();
+        db.put(b"k1", b"v3").unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v3");
+    }
+}
+
+#[test]
+fn test_set_bloom_locality_and_memtable_huge_page_size() {
+    let path = DBPath::new("_rust_rocksdb_test_set_bloom_locality");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_bloom_locality(1);
+        // falls back to malloc when no huge pages are reserved
+        opts.set_memtable_huge_page_size(2 * 1024 * 1024);
+        let db = DB::open(&opts, &path).unwrap();
+
+        let settings = read_log(&path);
+        assert!(settings.contains("Options.bloom_locality: 1"));
+        assert!(settings.contains("Options.memtable_huge_page_size: 2097152"));
+
+        put_keys(&db, 0..100);
+        assert_keys(&db, 0..100);
+    }
+}
+
+#[test]
+fn test_set_wal_recovery_mode() {
+    let path = DBPath::new("_rust_rocksdb_test_set_wal_recovery_mode");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_wal_recovery_mode(DBRecoveryMode::PointInTime);
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+    }
+    {
+        // the unflushed write is replayed from the WAL on reopen
+        let db = DB::open(&opts, &path).unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+}
+
+#[test]
+fn test_set_access_hint_and_advise_random_on_open() {
+    let path = DBPath::new("_rust_rocksdb_test_set_access_hint");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_access_hint_on_compaction_start(AccessHint::Sequential);
+    opts.set_advise_random_on_open(false);
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        let settings = read_log(&path);
+        // AccessHint::Sequential
+        assert!(settings.contains("Options.access_hint_on_compaction_start: 2"));
+        assert!(settings.contains("Options.advise_random_on_open: 0"));
+
+        put_keys(&db, 0..100);
+        db.flush().unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+    }
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        assert_keys(&db, 0..100);
+    }
+}
+
+#[test]
+fn test_set_stats_dump_period_sec_and_dump_malloc_stats() {
+    let path = DBPath::new("_rust_rocksdb_test_set_stats_dump_period_sec");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_stats_dump_period_sec(1);
+        opts.set_dump_malloc_stats(true);
+        let db = DB::open(&opts, &path).unwrap();
+
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+}
+
+#[test]
+fn test_set_paranoid_checks() {
+    let path = DBPath::new("_rust_rocksdb_test_set_paranoid_checks");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_paranoid_checks(true);
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        assert!(read_log(&path).contains("Options.paranoid_checks: 1"));
+
+        put_keys(&db, 0..100);
+        db.flush().unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+    }
+    {
+        // a healthy database reopens and reads without errors
+        let db = DB::open(&opts, &path).unwrap();
+        assert_keys(&db, 0..100);
+    }
+    {
+        // true is the default, so also check that false reaches RocksDB
+        opts.set_paranoid_checks(false);
+        let db = DB::open(&opts, &path).unwrap();
+        assert!(read_log(&path).contains("Options.paranoid_checks: 0"));
+        assert_keys(&db, 0..100);
+    }
+}
+
+#[test]
+fn test_set_bytes_per_sync() {
+    let path = DBPath::new("_rust_rocksdb_test_set_bytes_per_sync");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_bytes_per_sync(1024 * 1024);
+        opts.set_wal_bytes_per_sync(1024 * 1024);
+        let db = DB::open(&opts, &path).unwrap();
+
+        let value = vec![b'x'; 1024];
+        let mut batch = WriteBatch::default();
+        for i in 0..4096 {
+            batch.put(format!("key{:05}", i), &value);
+        }
+        db.write(batch).unwrap();
+        db.flush().unwrap();
+        assert_eq!(db.get(b"key04095").unwrap().unwrap(), value);
+    }
+}
+
+#[test]
+fn test_set_vector_memtable_factory() {
+    let path = DBPath::new("_rust_rocksdb_test_set_vector_memtable_factory");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    // the vector memtable does not support concurrent inserts
+    opts.set_allow_concurrent_memtable_write(false);
+    opts.set_memtable_factory(MemtableFactory::Vector);
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        let mut batch = WriteBatch::default();
+        for i in (0..1000).rev() {
+            batch.put(format!("key{:04}", i), format!("value{:04}", i));
+        }
+        db.write(batch).unwrap();
+        db.flush().unwrap();
+    }
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        for i in 0..1000 {
+            assert_eq!(
+                db.get(format!("key{:04}", i)).unwrap().unwrap(),
+                format!("value{:04}", i).as_bytes()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_set_skip_checking_sst_file_sizes_on_db_open() {
+    let path = DBPath::new("_rust_rocksdb_test_skip_checking_sst_file_sizes");
+    {
+        let db = DB::open_default(&path).unwrap();
+        for start in (0..100).step_by(25) {
+            put_keys(&db, start..start + 25);
+            db.flush().unwrap();
+        }
+    }
+    {
+        let mut opts = Options::default();
+        opts.set_skip_checking_sst_file_sizes_on_db_open(true);
+        opts.set_skip_stats_update_on_db_open(true);
+        let db = DB::open(&opts, &path).unwrap();
+        let settings = read_log(&path);
+        assert!(settings.contains("Options.skip_checking_sst_file_sizes_on_db_open: 1"));
+        assert!(settings.contains("Options.skip_stats_update_on_db_open: 1"));
+        assert_keys(&db, 0..100);
+    }
+}
+
+#[test]
+fn test_set_block_cache_compressed() {
+    let path = DBPath::new("_rust_rocksdb_test_set_block_cache_compressed");
+    {
+        let cache = Cache::new_lru_cache(512 << 10).unwrap();
+        let compressed_cache = Cache::new_lru_cache(1 << 20).unwrap();
+        let mut block_opts = BlockBasedOptions::default();
+        block_opts.set_block_cache(&cache);
+        block_opts.set_block_cache_compressed(&compressed_cache);
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_block_based_table_factory(&block_opts);
+        let db = DB::open(&opts, &path).unwrap();
+
+        for i in 0..1000 {
+            db.put(format!("key{:04}", i), format!("value{:04}", i))
+                .unwrap();
+        }
+        db.flush().unwrap();
+
+        for i in 0..1000 {
+            assert_eq!(
+                db.get(format!("key{:04}", i)).unwrap().unwrap(),
+                format!("value{:04}", i).as_bytes()
+            );
+        }
+        assert!(cache.get_usage() > 0);
+    }
+}
+
+#[test]
+fn test_validate_write_buffer_numbers() {
+    let mut opts = Options::default();
+    assert_eq!(opts.max_write_buffer_number(), None);
+    assert!(opts.validate().is_ok());
+
+    opts.set_max_write_buffer_number(1);
+    opts.set_min_write_buffer_number_to_merge(2);
+    assert_eq!(opts.max_write_buffer_number(), Some(1));
+    assert!(opts.validate().is_err());
+
+    // the cached values are carried over by clone
+    let mut cloned = opts.clone();
+    assert_eq!(cloned.max_write_buffer_number(), Some(1));
+    assert!(cloned.validate().is_err());
+
+    cloned.set_max_write_buffer_number(4);
+    assert!(cloned.validate().is_ok());
+}
+
+#[test]
+fn test_validate_after_optimize_compaction() {
+    // the optimize helpers raise max_write_buffer_number to 6
+    let mut opts = Options::default();
+    opts.set_max_write_buffer_number(2);
+    opts.optimize_level_style_compaction(512 * 1024 * 1024);
+    assert_eq!(opts.max_write_buffer_number(), Some(6));
+    opts.set_min_write_buffer_number_to_merge(3);
+    assert!(opts.validate().is_ok());
+
+    let mut opts = Options::default();
+    opts.optimize_universal_style_compaction(512 * 1024 * 1024);
+    assert_eq!(opts.max_write_buffer_number(), Some(6));
+    opts.set_min_write_buffer_number_to_merge(3);
+    assert!(opts.validate().is_ok());
+    opts.set_min_write_buffer_number_to_merge(6);
+    assert!(opts.validate().is_err());
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_prefer_unified_background_jobs() {
+    {
+        let path = DBPath::new("_rust_rocksdb_test_deprecated_background_setters");
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_max_background_compactions(4);
+        opts.set_max_background_flushes(2);
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+    {
+        let path = DBPath::new("_rust_rocksdb_test_prefer_unified_background_jobs");
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.prefer_unified_background_jobs(true);
+        opts.set_max_background_jobs(6);
+        // ignored in favor of max_background_jobs
+        opts.set_max_background_compactions(4);
+        opts.set_max_background_flushes(3);
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+
+        let mut rocksdb_log = fs::File::open(format!("{}/LOG", (&path).as_ref().to_str().unwrap()))
+            .expect("rocksdb creates a LOG file");
+        let mut settings = String::new();
+        rocksdb_log.read_to_string(&mut settings).unwrap();
+        assert!(settings.contains("Options.max_background_jobs: 6"));
+        assert!(settings.contains("Options.max_background_compactions: -1"));
+        assert!(settings.contains("Options.max_background_flushes: -1"));
+    }
+}
+
+#[test]
+fn test_set_enable_write_thread_adaptive_yield() {
+    let path = DBPath::new("_rust_rocksdb_test_write_thread_adaptive_yield");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_enable_write_thread_adaptive_yield(true);
+        let db = Arc::new(DB::open(&opts, &path).unwrap());
+
+        let handles: Vec<_> = (0..8)
+            .map(|t| {
+                let db = db.clone();
+                thread::spawn(move || {
+                    for i in 0..500 {
+                        db.put(format!("key{}-{:03}", t, i), b"value").unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        for t in 0..8 {
+            for i in 0..500 {
+                assert!(db.get(format!("key{}-{:03}", t, i)).unwrap().is_some());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_set_table_cache_num_shard_bits() {
+    let path = DBPath::new("_rust_rocksdb_test_set_table_cache_num_shard_bits");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        assert_eq!(opts.table_cache_num_shard_bits(), None);
+        opts.set_table_cache_num_shard_bits(8);
+        assert_eq!(opts.table_cache_num_shard_bits(), Some(8));
+
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+
+        let mut rocksdb_log = fs::File::open(format!("{}/LOG", (&path).as_ref().to_str().unwrap()))
+            .expect("rocksdb creates a LOG file");
+        let mut settings = String::new();
+        rocksdb_log.read_to_string(&mut settings).unwrap();
+        assert!(settings.contains("Options.table_cache_numshardbits: 8"));
+    }
+}
+
+#[test]
+fn test_set_max_file_opening_threads() {
+    let path = DBPath::new("_rust_rocksdb_test_set_max_file_opening_threads");
+    {
+        let db = DB::open_default(&path).unwrap();
+        for start in (0..100).step_by(10) {
+            put_keys(&db, start..start + 10);
+            db.flush().unwrap();
+        }
+    }
+    {
+        let mut opts = Options::default();
+        opts.set_max_file_opening_threads(8);
+        let db = DB::open(&opts, &path).unwrap();
+        assert!(read_log(&path).contains("Options.max_file_opening_threads: 8"));
+        assert_keys(&db, 0..100);
+    }
+}
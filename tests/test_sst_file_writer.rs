@@ -16,7 +16,7 @@ mod util;
 
 use pretty_assertions::assert_eq;
 
-use rocksdb::{Error, Options, SstFileWriter, DB};
+use rocksdb::{Error, IngestExternalFileOptions, Options, SstFileWriter, DB};
 use util::DBPath;
 
 #[test]
@@ -50,3 +50,84 @@ fn sst_file_writer_works() {
         assert!(db.get(b"k3").unwrap().is_none());
     }
 }
+
+#[test]
+fn bulk_ingest_works() {
+    let db_path = DBPath::new("_rust_rocksdb_bulk_ingest_test");
+    {
+        let opts = Options::default();
+        let db = DB::open_default(&db_path).unwrap();
+
+        let items = (0..10_000).map(|i| (format!("key{:05}", i), format!("value{:05}", i)));
+        db.bulk_ingest(items, &opts).unwrap();
+
+        for i in &[0, 1, 4999, 9999] {
+            assert_eq!(
+                db.get(format!("key{:05}", i)).unwrap().unwrap(),
+                format!("value{:05}", i).as_bytes()
+            );
+        }
+        assert!(db.get(b"key10000").unwrap().is_none());
+
+        let unsorted = vec![(b"b", b"2"), (b"a", b"1")];
+        assert!(db.bulk_ingest(unsorted, &opts).is_err());
+        assert!(db.get(b"a").unwrap().is_none());
+
+        let panicking = (0..10).map(|i| {
+            if i == 5 {
+                panic!("failed to produce item");
+            }
+            (format!("p{}", i), b"value")
+        });
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            db.bulk_ingest(panicking, &opts)
+        }));
+        assert!(result.is_err());
+        assert!(db.get(b"p0").unwrap().is_none());
+
+        // the temporary files are cleaned up
+        let leftovers = std::fs::read_dir(&db_path)
+            .unwrap()
+            .filter(|entry| {
+                let name = entry.as_ref().unwrap().file_name();
+                name.to_str().unwrap().starts_with("bulk_ingest_")
+            })
+            .count();
+        assert_eq!(leftovers, 0);
+    }
+}
+
+#[test]
+fn ingest_behind_works() {
+    let db_path = DBPath::new("_rust_rocksdb_ingest_behind_test");
+    let dir = tempfile::Builder::new()
+        .prefix("_rust_rocksdb_ingest_behind_test")
+        .tempdir()
+        .expect("Failed to create temporary path for file writer.");
+    let writer_path = dir.path().join("filewriter");
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_allow_ingest_behind(true);
+    {
+        let db = DB::open(&opts, &db_path).unwrap();
+        db.put(b"k2", b"new").unwrap();
+        db.put(b"k3", b"new").unwrap();
+
+        // older data, some of which has been overwritten since
+        let mut writer = SstFileWriter::create(&opts);
+        writer.open(&writer_path).unwrap();
+        writer.put(b"k1", b"old").unwrap();
+        writer.put(b"k2", b"old").unwrap();
+        writer.finish().unwrap();
+
+        let mut ingest_opts = IngestExternalFileOptions::default();
+        ingest_opts.set_ingest_behind(true);
+        db.ingest_external_file_opts(&ingest_opts, vec![&writer_path])
+            .unwrap();
+
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"old");
+        assert_eq!(db.get(b"k2").unwrap().unwrap(), b"new");
+        assert_eq!(db.get(b"k3").unwrap().unwrap(), b"new");
+    }
+}
@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod util;
+
 use pretty_assertions::assert_eq;
 
-use rocksdb::WriteBatch;
+use rocksdb::{WriteBatch, WriteBatchIterator, WriteOptions, DB};
+use util::DBPath;
 
 #[test]
 fn test_write_batch_clear() {
@@ -25,3 +28,147 @@ fn test_write_batch_clear() {
     assert_eq!(batch.len(), 0);
     assert!(batch.is_empty());
 }
+
+#[test]
+fn test_write_batch_append() {
+    let path = DBPath::new("_rust_rocksdb_test_write_batch_append");
+    {
+        let db = DB::open_default(&path).unwrap();
+
+        let mut batch = WriteBatch::default();
+        batch.put(b"k1", b"v1");
+        batch.put(b"k2", b"v2");
+
+        let mut other = WriteBatch::default();
+        other.put(b"k3", b"v3");
+        other.delete(b"k1");
+
+        batch.append(&other);
+        assert_eq!(batch.len(), 4);
+        batch.append(&WriteBatch::default());
+        assert_eq!(batch.len(), 4);
+
+        db.write(batch).unwrap();
+        assert!(db.get(b"k1").unwrap().is_none());
+        assert_eq!(db.get(b"k2").unwrap().unwrap(), b"v2");
+        assert_eq!(db.get(b"k3").unwrap().unwrap(), b"v3");
+    }
+}
+
+#[derive(Default)]
+struct RecordedOps {
+    ops: Vec<(Box<[u8]>, Option<Box<[u8]>>)>,
+}
+
+impl WriteBatchIterator for RecordedOps {
+    fn put(&mut self, key: Box<[u8]>, value: Box<[u8]>) {
+        self.ops.push((key, Some(value)));
+    }
+
+    fn delete(&mut self, key: Box<[u8]>) {
+        self.ops.push((key, None));
+    }
+}
+
+#[test]
+fn test_write_batch_append_iterate() {
+    let mut batch = WriteBatch::default();
+    batch.put(b"k1", b"v1");
+    batch.delete(b"k2");
+
+    let mut other = WriteBatch::default();
+    other.put(b"k3", b"v3");
+    other.delete(b"k1");
+    batch.append(&other);
+
+    // the patched header count must cover every appended record
+    let mut recorded = RecordedOps::default();
+    batch.iterate(&mut recorded);
+    let ops: Vec<(&[u8], Option<&[u8]>)> = recorded
+        .ops
+        .iter()
+        .map(|(k, v)| (&k[..], v.as_deref()))
+        .collect();
+    assert_eq!(
+        ops,
+        vec![
+            (&b"k1"[..], Some(&b"v1"[..])),
+            (&b"k2"[..], None),
+            (&b"k3"[..], Some(&b"v3"[..])),
+            (&b"k1"[..], None),
+        ]
+    );
+}
+
+#[test]
+fn test_write_batch_from_data() {
+    let mut batch = WriteBatch::default();
+    batch.put(b"k1", b"v1");
+
+    let copy = WriteBatch::from_data(batch.data()).unwrap();
+    assert_eq!(copy.len(), 1);
+    assert_eq!(copy.data(), batch.data());
+}
+
+#[test]
+fn test_write_batch_from_data_too_short() {
+    assert!(WriteBatch::from_data(&[]).is_err());
+    assert!(WriteBatch::from_data(&[0; 11]).is_err());
+    assert!(WriteBatch::from_data(&[0; 12]).unwrap().is_empty());
+}
+
+#[test]
+fn test_write_batch_delete_keys() {
+    let path = DBPath::new("_rust_rocksdb_test_write_batch_delete_keys");
+    {
+        let db = DB::open_default(&path).unwrap();
+        let keys: Vec<_> = (0..10).map(|i| format!("key{}", i)).collect();
+        for key in &keys {
+            db.put(key, b"value").unwrap();
+        }
+        db.put(b"other", b"value").unwrap();
+
+        let mut batch = WriteBatch::default();
+        batch.delete_keys(&keys);
+        assert_eq!(batch.len(), keys.len());
+        db.write(batch).unwrap();
+
+        for key in &keys {
+            assert!(db.get(key).unwrap().is_none());
+        }
+        assert!(db.get(b"other").unwrap().is_some());
+    }
+}
+
+#[test]
+fn test_db_batch_closure() {
+    let path = DBPath::new("_rust_rocksdb_test_db_batch_closure");
+    {
+        let db = DB::open_default(&path).unwrap();
+        let writeopts = WriteOptions::default();
+
+        db.batch(&writeopts, |batch| {
+            for i in 0..10 {
+                batch.put(format!("key{}", i), format!("value{}", i));
+            }
+            Ok(())
+        })
+        .unwrap();
+        for i in 0..10 {
+            assert_eq!(
+                db.get(format!("key{}", i)).unwrap().unwrap(),
+                format!("value{}", i).as_bytes()
+            );
+        }
+
+        // an error from inside the closure, here a malformed serialized
+        // batch, stops the write
+        let result = db.batch(&writeopts, |batch| {
+            batch.put(b"never", b"written");
+            batch.append(&WriteBatch::from_data(&[])?);
+            Ok(())
+        });
+        assert!(result.is_err());
+        assert!(db.get(b"never").unwrap().is_none());
+    }
+}